@@ -4,10 +4,15 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::borrow::Cow;
 use std::fmt::{self, Display};
 use std::path::Path;
+use std::str::FromStr;
 
-use crate::{Extension, Identifier, Keywords, Signature, Title, regex};
+use crate::{
+    Component, ComponentsOrder, Error, Extension, FrontMatter, FrontMatterKind, Identifier,
+    Keywords, LinkStyle, Signature, SlugOptions, Title, format, regex,
+};
 
 /// Handle denote name scheme
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
@@ -29,6 +34,8 @@ impl Denote {
     }
 
     /// Trying find denote name scheme in file name. Returns `None` if name scheme didn't found.
+    /// Components may go in any order. Identifier which is not the first component must be
+    /// prefixed with "@@".
     ///
     /// # Examples
     ///
@@ -40,44 +47,124 @@ impl Denote {
     /// let mut name_scheme = Denote::from_path(path).unwrap();
     /// name_scheme.title = Title::parse("Another title");
     /// assert_eq!(name_scheme.to_string(), "20240903T13173023--another-title__keyword.txt");
+    ///
+    /// let path = "--title@@20240903T13173023__keyword.txt";
+    /// assert_eq!(Denote::from_path(path), Denote::from_path("20240903T13173023--title__keyword.txt"));
     /// ```
     pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
-        fn inner(path: &Path) -> Option<Denote> {
-            let file_name = path.file_name()?.to_str()?.to_string();
+        Self::try_from_path(path).ok()
+    }
 
-            let captures = regex::NAME_SCHEME.captures(&file_name)?;
+    /// Same as `from_path` but returns error which explains why file name doesn't follow the
+    /// name scheme
+    ///
+    /// ```
+    /// use zeroten_denote::{Denote, Error};
+    ///
+    /// assert!(matches!(
+    ///     Denote::try_from_path("some file.txt"),
+    ///     Err(Error::NotDenoteName(_))
+    /// ));
+    /// assert!(matches!(
+    ///     Denote::try_from_path("20241399T99999999--title.txt"),
+    ///     Err(Error::ImpossibleDate(_))
+    /// ));
+    /// ```
+    pub fn try_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::try_from_path_with(path, &SlugOptions::default())
+    }
 
-            let id = {
-                let capture = captures.name("id").unwrap();
-                Identifier::parse(capture.as_str())?
-            };
+    /// Same as `from_path` but file name is normalized only if `options.normalize` is set, so
+    /// a name in normalization form D can be parsed without repairing it
+    ///
+    /// ```
+    /// use zeroten_denote::{Denote, SlugOptions};
+    ///
+    /// let nfd = "20240912T130154--cafe\u{301}.txt";
+    /// let options = SlugOptions {
+    ///     normalize: false,
+    ///     ..SlugOptions::default()
+    /// };
+    /// let denote = Denote::from_path_with(nfd, &options).unwrap();
+    /// assert_eq!(denote.to_string(), nfd);
+    /// ```
+    pub fn from_path_with<P: AsRef<Path>>(path: P, options: &SlugOptions) -> Option<Self> {
+        Self::try_from_path_with(path, options).ok()
+    }
 
-            let mut name_scheme = Denote::new(id);
+    /// Same as `from_path_with` but returns error which explains why file name doesn't follow
+    /// the name scheme
+    pub fn try_from_path_with<P: AsRef<Path>>(
+        path: P,
+        options: &SlugOptions,
+    ) -> Result<Self, Error> {
+        fn inner(path: &Path, options: &SlugOptions) -> Result<Denote, Error> {
+            parse_name(name(path)?, true, options.normalize)
+        }
 
-            name_scheme.signature = captures
-                .name("signature")
-                .map(|c| c.as_str())
-                .and_then(Signature::parse);
+        inner(path.as_ref(), options)
+    }
 
-            name_scheme.title = captures
-                .name("title")
-                .map(|c| c.as_str())
-                .and_then(Title::parse);
+    /// Same as `from_path` but for directories. Directory names never have extension, so a
+    /// name with a dot doesn't follow the name scheme.
+    ///
+    /// ```
+    /// use zeroten_denote::Denote;
+    ///
+    /// let denote = Denote::from_dir_path("20240912T130154--project__work").unwrap();
+    /// assert_eq!(denote.extension, None);
+    /// assert_eq!(denote.to_string(), "20240912T130154--project__work");
+    ///
+    /// assert!(Denote::from_path("20240912T130154--project.old").unwrap().extension.is_some());
+    /// assert_eq!(Denote::from_dir_path("20240912T130154--project.old"), None);
+    /// ```
+    pub fn from_dir_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        Self::try_from_dir_path(path).ok()
+    }
 
-            name_scheme.keywords = captures
-                .name("keywords")
-                .map(|c| c.as_str())
-                .and_then(Keywords::parse_schemed_string);
+    /// Same as `from_dir_path` but returns error which explains why directory name doesn't
+    /// follow the name scheme
+    pub fn try_from_dir_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::try_from_dir_path_with(path, &SlugOptions::default())
+    }
 
-            name_scheme.extension = captures
-                .name("ext")
-                .map(|c| c.as_str())
-                .and_then(Extension::new);
+    /// Same as `from_dir_path` but directory name is normalized only if `options.normalize`
+    /// is set
+    pub fn from_dir_path_with<P: AsRef<Path>>(path: P, options: &SlugOptions) -> Option<Self> {
+        Self::try_from_dir_path_with(path, options).ok()
+    }
 
-            Some(name_scheme)
+    /// Same as `from_dir_path_with` but returns error which explains why directory name
+    /// doesn't follow the name scheme
+    pub fn try_from_dir_path_with<P: AsRef<Path>>(
+        path: P,
+        options: &SlugOptions,
+    ) -> Result<Self, Error> {
+        fn inner(path: &Path, options: &SlugOptions) -> Result<Denote, Error> {
+            parse_name(name(path)?, false, options.normalize)
         }
 
-        inner(path.as_ref())
+        inner(path.as_ref(), options)
+    }
+
+    /// Check if file name is in Unicode normalization form C. `from_path` normalizes components,
+    /// so a file with not normalized name can be repaired by renaming it to `to_string()`
+    /// result.
+    ///
+    /// ```
+    /// use zeroten_denote::Denote;
+    ///
+    /// let nfd = "20240912T130154--cafe\u{301}.txt";
+    /// assert!(!Denote::is_normalized_name(nfd));
+    /// let denote = Denote::from_path(nfd).unwrap();
+    /// assert_eq!(denote.to_string(), "20240912T130154--caf\u{e9}.txt");
+    /// assert!(Denote::is_normalized_name(denote.to_string()));
+    /// ```
+    pub fn is_normalized_name<P: AsRef<Path>>(path: P) -> bool {
+        path.as_ref()
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_none_or(unicode_normalization::is_nfc)
     }
 
     /// Set signature
@@ -103,28 +190,229 @@ impl Denote {
         self.extension = Some(extension);
         self
     }
+
+    /// Format denote name scheme with given order of components. Identifier is prefixed with
+    /// "@@" if it's not the first component.
+    ///
+    /// ```
+    /// use zeroten_denote::{ComponentsOrder, Denote};
+    ///
+    /// let denote = Denote::from_path("20240912T130154--some-title__kw.org").unwrap();
+    /// let order = ComponentsOrder::parse("title,signature,identifier,keywords").unwrap();
+    /// assert_eq!(
+    ///     denote.to_string_with_order(&order),
+    ///     "--some-title@@20240912T130154__kw.org"
+    /// );
+    /// ```
+    pub fn to_string_with_order(&self, order: &ComponentsOrder) -> String {
+        let mut name = String::new();
+
+        for component in order.iter() {
+            match component {
+                Component::Identifier => {
+                    name.push_str(component.prefix());
+                    name.push_str(&self.identifier.to_string());
+                }
+                Component::Signature => {
+                    if let Some(signature) = &self.signature {
+                        name.push_str(&signature.to_string());
+                    }
+                }
+                Component::Title => {
+                    if let Some(title) = &self.title {
+                        name.push_str(&title.to_string());
+                    }
+                }
+                Component::Keywords => {
+                    if let Some(keywords) = &self.keywords {
+                        name.push_str(&keywords.to_string());
+                    }
+                }
+            }
+        }
+
+        if let Some(extension) = &self.extension {
+            name.push_str(&extension.to_string());
+        }
+
+        // Identifier is not prefixed if it's the first component
+        name.strip_prefix(Component::Identifier.prefix())
+            .map(ToString::to_string)
+            .unwrap_or(name)
+    }
+
+    /// Render front matter matching the extension. Returns `None` if extension is not set or
+    /// Emacs Denote doesn't have front matter for it.
+    ///
+    /// ```
+    /// use zeroten_denote::{Denote, Extension, Identifier, Title};
+    ///
+    /// let identifier = Identifier::parse("20240912T13015412").unwrap();
+    /// let front_matter = Denote::new(identifier)
+    ///     .title(Title::parse("Some title").unwrap())
+    ///     .extension(Extension::new("org").unwrap())
+    ///     .front_matter()
+    ///     .unwrap();
+    /// assert!(front_matter.starts_with("#+title:      Some title\n"));
+    /// ```
+    pub fn front_matter(&self) -> Option<String> {
+        let kind = FrontMatterKind::from_extension(self.extension.as_ref()?)?;
+        Some(FrontMatter::from(self).render(kind))
+    }
+
+    /// Link to this note in given style. Search is appended to identifier after "::", e.g. a
+    /// heading like "*Intro" in Org.
+    ///
+    /// ```
+    /// use zeroten_denote::{Denote, LinkStyle};
+    ///
+    /// let denote: Denote = "20240912T13015412--some-title.org".parse().unwrap();
+    /// assert_eq!(
+    ///     denote.link(LinkStyle::Org, None),
+    ///     "[[denote:20240912T13015412][Some title]]"
+    /// );
+    /// assert_eq!(
+    ///     denote.link(LinkStyle::Markdown, Some("intro")),
+    ///     "[Some title](denote:20240912T13015412::intro)"
+    /// );
+    /// ```
+    pub fn link(&self, style: LinkStyle, search: Option<&str>) -> String {
+        let target = search.map_or_else(
+            || format!("denote:{}", self.identifier),
+            |search| format!("denote:{}::{search}", self.identifier),
+        );
+        let title = self.title.as_ref().map(Title::desluggify);
+        match (style, title) {
+            (LinkStyle::Org, Some(title)) => format!("[[{target}][{title}]]"),
+            (LinkStyle::Org, None) => format!("[[{target}]]"),
+            (LinkStyle::Markdown, Some(title)) => format!("[{title}]({target})"),
+            (LinkStyle::Markdown, None) => format!("<{target}>"),
+            (LinkStyle::Text, _) => target,
+        }
+    }
 }
 
 impl Display for Denote {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.identifier)?;
+        self.to_string_with_order(&ComponentsOrder::default())
+            .fmt(f)
+    }
+}
+
+impl FromStr for Denote {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_file_name(s)
+    }
+}
+
+impl TryFrom<&Path> for Denote {
+    type Error = Error;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        Self::try_from_path(path)
+    }
+}
 
-        if let Some(signature) = &self.signature {
-            write!(f, "{signature}")?;
+fn parse_file_name(file_name: &str) -> Result<Denote, Error> {
+    parse_name(file_name, true, true)
+}
+
+fn name(path: &Path) -> Result<&str, Error> {
+    let name = path.file_name().ok_or(Error::NoFileName)?;
+    name.to_str().ok_or(Error::NonUtf8FileName)
+}
+
+/// Parse file or directory name. Directory names don't have extension.
+fn parse_name(file_name: &str, with_extension: bool, normalize: bool) -> Result<Denote, Error> {
+    // Names synced from macOS may be in normalization form D
+    let file_name: Cow<str> = if normalize {
+        format::nfc(file_name)
+    } else {
+        file_name.into()
+    };
+    let file_name = file_name.as_ref();
+
+    let (stem, extension) = match file_name.split_once('.').filter(|_| with_extension) {
+        Some((stem, ext)) => (stem, Some(ext)),
+        None => (file_name, None),
+    };
+
+    let components = components(stem).ok_or_else(|| Error::NotDenoteName(file_name.to_string()))?;
+
+    let mut identifier = None;
+    let mut signature = None;
+    let mut title = None;
+    let mut keywords = None;
+    let mut found = Vec::new();
+
+    for (component, value) in components {
+        if found.contains(&component) {
+            return Err(Error::DuplicateComponent(component));
         }
+        found.push(component);
 
-        if let Some(title) = &self.title {
-            write!(f, "{title}")?;
+        let regex = match component {
+            Component::Identifier => &regex::WHOLE_IDENTIFIER,
+            Component::Signature => &regex::SIGNATURE,
+            Component::Title => &regex::TITLE,
+            Component::Keywords => &regex::KEYWORDS,
+        };
+        if !regex.is_match(value) {
+            return Err(Error::InvalidComponent(component, value.to_string()));
         }
 
-        if let Some(keywords) = &self.keywords {
-            write!(f, "{keywords}")?;
+        match component {
+            Component::Identifier => identifier = Some(Identifier::parse_exact(value)?),
+            Component::Signature => signature = Some(Signature::from_slug(value.to_string())),
+            Component::Title => title = Some(Title::from_slug(value.to_string())),
+            Component::Keywords => keywords = Some(Keywords::from_slug(value)),
         }
+    }
 
-        if let Some(extension) = &self.extension {
-            write!(f, "{extension}")?;
+    let identifier = identifier.ok_or(Error::MissingIdentifier)?;
+
+    let extension = match extension {
+        Some(ext) if !regex::EXTENSION.is_match(ext) => {
+            return Err(Error::InvalidExtension(ext.to_string()));
         }
+        Some(ext) => Some(Extension::try_new(ext)?),
+        None => None,
+    };
+
+    Ok(Denote {
+        identifier,
+        signature,
+        title,
+        keywords,
+        extension,
+    })
+}
+
+/// Split file name stem in to components and their values
+fn components(stem: &str) -> Option<Vec<(Component, &str)>> {
+    let mut components = Vec::new();
+
+    let leading_identifier = regex::LEADING_IDENTIFIER.find(stem);
+    if let Some(id) = leading_identifier {
+        components.push((Component::Identifier, id.as_str()));
+    }
+    let rest = leading_identifier.map_or(stem, |id| &stem[id.end()..]);
+
+    let delimiters: Vec<_> = regex::DELIMITER.find_iter(rest).collect();
 
-        fmt::Result::Ok(())
+    if !rest.is_empty() && delimiters.first().is_none_or(|d| d.start() != 0) {
+        return None;
     }
+
+    for (i, delimiter) in delimiters.iter().enumerate() {
+        let end = delimiters
+            .get(i + 1)
+            .map_or(rest.len(), ::regex::Match::start);
+        let component = Component::from_prefix(delimiter.as_str())?;
+        components.push((component, &rest[delimiter.end()..end]));
+    }
+
+    Some(components)
 }
@@ -0,0 +1,76 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{self, Display};
+
+use crate::Component;
+
+/// Reason why string can't be parsed in to denote name scheme or its component
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// Path doesn't have a file name
+    NoFileName,
+    /// File name is not valid UTF-8
+    NonUtf8FileName,
+    /// File name doesn't follow denote name scheme
+    NotDenoteName(String),
+    /// File name doesn't contain identifier
+    MissingIdentifier,
+    /// Component occurs in file name more than once
+    DuplicateComponent(Component),
+    /// Component in file name contains characters which are not allowed
+    InvalidComponent(Component, String),
+    /// String is neither identifier nor date
+    InvalidIdentifier(String),
+    /// Identifier represents a date which doesn't exist, e.g. "20241399T99999999"
+    ImpossibleDate(String),
+    /// Title became empty after slugifying
+    EmptyTitle,
+    /// Signature became empty after slugifying
+    EmptySignature,
+    /// Keywords became empty after slugifying
+    EmptyKeywords,
+    /// Extension is empty
+    EmptyExtension,
+    /// Extension contains characters which are not allowed
+    InvalidExtension(String),
+    /// Query term has unknown key or invalid value
+    InvalidQuery(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoFileName => write!(f, "path doesn't have a file name"),
+            Self::NonUtf8FileName => write!(f, "file name is not valid UTF-8"),
+            Self::NotDenoteName(name) => {
+                write!(f, "\"{name}\" doesn't follow denote name scheme")
+            }
+            Self::MissingIdentifier => write!(f, "file name doesn't contain identifier"),
+            Self::DuplicateComponent(component) => {
+                write!(f, "{component} occurs in file name more than once")
+            }
+            Self::InvalidComponent(component, value) => {
+                write!(f, "\"{value}\" is not valid {component}")
+            }
+            Self::InvalidIdentifier(string) => {
+                write!(f, "\"{string}\" is neither identifier nor date")
+            }
+            Self::ImpossibleDate(id) => {
+                write!(f, "identifier \"{id}\" represents impossible date")
+            }
+            Self::EmptyTitle => write!(f, "title is empty"),
+            Self::EmptySignature => write!(f, "signature is empty"),
+            Self::EmptyKeywords => write!(f, "keywords are empty"),
+            Self::EmptyExtension => write!(f, "extension is empty"),
+            Self::InvalidExtension(ext) => write!(f, "\"{ext}\" is not valid extension"),
+            Self::InvalidQuery(term) => write!(f, "\"{term}\" is not valid query term"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -6,6 +6,9 @@
 
 use std::fmt::{self, Display};
 use std::path::Path;
+use std::str::FromStr;
+
+use crate::Error;
 
 /// Represent file extension
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -13,13 +16,18 @@ pub struct Extension(String);
 
 impl Extension {
     pub fn new<S: AsRef<str>>(ext: S) -> Option<Self> {
-        fn inner(ext: &str) -> Option<Extension> {
+        Self::try_new(ext).ok()
+    }
+
+    /// Same as `new` but returns error if extension is empty
+    pub fn try_new<S: AsRef<str>>(ext: S) -> Result<Self, Error> {
+        fn inner(ext: &str) -> Result<Extension, Error> {
             let ext = ext.trim();
             let ext = ext.strip_prefix(".").unwrap_or(ext);
             if ext.is_empty() {
-                return None;
+                return Err(Error::EmptyExtension);
             }
-            Some(Extension(ext.to_string()))
+            Ok(Extension(ext.to_string()))
         }
         inner(ext.as_ref())
     }
@@ -32,6 +40,19 @@ impl Extension {
 
         inner(path.as_ref())
     }
+
+    /// Extension without leading dot
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Extension {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_new(s)
+    }
 }
 
 impl Display for Extension {
@@ -6,6 +6,8 @@
 
 use std::borrow::Cow;
 
+use unicode_normalization::UnicodeNormalization;
+
 /// Makes first letter in string uppercase
 pub fn first_letter_uppercase<'a, S>(string: S) -> Cow<'a, str>
 where
@@ -29,34 +31,110 @@ where
     inner(string.into())
 }
 
-pub fn slugify<S: AsRef<str>>(s: S, separator: Option<char>) -> String {
-    fn inner(s: &str, separator: Option<char>) -> String {
+/// Convert string to Unicode normalization form C. Doesn't allocate if string is already
+/// normalized.
+pub fn nfc(s: &str) -> Cow<'_, str> {
+    if unicode_normalization::is_nfc(s) {
+        s.into()
+    } else {
+        s.nfc().collect::<String>().into()
+    }
+}
+
+/// Punctuation which Emacs Denote removes from file names. See
+/// `denote-excluded-punctuation-regexp`.
+const EXCLUDED_PUNCTUATION: &str = "[]{}<>!@#$%^&*()=+'\"\\?,.|;:~`‘’“”/";
+
+/// Options of converting user input in to name scheme components. Default options produce the
+/// same result as Emacs Denote.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SlugOptions {
+    /// Don't convert letters to lowercase
+    pub preserve_case: bool,
+    /// Characters which are removed in addition to the default punctuation. Analogue of
+    /// `denote-excluded-punctuation-extra-regexp`.
+    pub extra_excluded: String,
+    /// Transliterate non-ASCII characters (diacritics, CJK and so on) to ASCII
+    pub transliterate: bool,
+    /// Keep "=" typed in signature as separator. Otherwise it's removed.
+    pub keep_signature_equals: bool,
+    /// Convert input to Unicode normalization form C, so the same text typed on different
+    /// systems gives the same bytes
+    pub normalize: bool,
+}
+
+impl SlugOptions {
+    fn is_excluded(&self, c: char) -> bool {
+        EXCLUDED_PUNCTUATION.contains(c) || self.extra_excluded.contains(c)
+    }
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        Self {
+            preserve_case: false,
+            extra_excluded: String::new(),
+            transliterate: false,
+            keep_signature_equals: true,
+            normalize: true,
+        }
+    }
+}
+
+/// Removes excluded punctuation and `remove` characters, replaces whitespace, underscores and
+/// `separator` with single `separator` and trims it.
+pub fn slugify<S: AsRef<str>>(
+    s: S,
+    separator: Option<char>,
+    remove: &str,
+    options: &SlugOptions,
+) -> String {
+    fn inner(s: &str, separator: Option<char>, remove: &str, options: &SlugOptions) -> String {
+        let s: Cow<str> = if options.normalize { nfc(s) } else { s.into() };
+        let s: Cow<str> = if options.transliterate {
+            deunicode::deunicode(&s).into()
+        } else {
+            s
+        };
         let mut slug = String::with_capacity(s.len());
-        let separator = separator.map(|c| c.to_string()).unwrap_or_default();
         // Starts with true to avoid leading separator
-        let mut prev_is_dash = true;
+        let mut prev_is_separator = true;
 
         for x in s.chars() {
-            if x.is_alphabetic() || x.is_numeric() {
-                slug.push_str(&x.to_lowercase().to_string());
-                prev_is_dash = false;
+            if remove.contains(x) {
                 continue;
             }
-            if !prev_is_dash {
-                slug.push_str(&separator);
-                prev_is_dash = true;
+            if x.is_whitespace() || x == '_' || Some(x) == separator {
+                if let Some(separator) = separator
+                    && !prev_is_separator
+                {
+                    slug.push(separator);
+                    prev_is_separator = true;
+                }
+                continue;
             }
+            if options.is_excluded(x) {
+                continue;
+            }
+            if options.preserve_case {
+                slug.push(x);
+            } else {
+                slug.extend(x.to_lowercase());
+            }
+            prev_is_separator = false;
         }
 
-        if !separator.is_empty() && slug.ends_with(&separator) {
+        if let Some(separator) = separator
+            && slug.ends_with(separator)
+        {
             slug.pop();
-            slug.shrink_to_fit();
         }
 
         slug
     }
 
-    inner(s.as_ref(), separator)
+    inner(s.as_ref(), separator, remove, options)
 }
 
 #[cfg(test)]
@@ -65,12 +143,66 @@ mod test {
 
     #[test]
     fn test() {
-        assert_eq!(slugify("Some title ", Some('-')), "some-title");
+        let options = SlugOptions::default();
         assert_eq!(
-            slugify("Some,keywords asd ", Some(',')),
+            slugify("Some title ", Some('-'), "", &options),
+            "some-title"
+        );
+        assert_eq!(
+            slugify("Some,keywords asd ", Some(','), "", &options),
             "some,keywords,asd"
         );
-        assert_eq!(slugify("empTy  separator", None), "emptyseparator");
-        assert_eq!(slugify("ddDDDD  ,  lll", Some('=')), "dddddd=lll");
+        assert_eq!(
+            slugify("empTy  separator", None, "", &options),
+            "emptyseparator"
+        );
+        assert_eq!(
+            slugify("ddDDDD  ,  lll", Some('='), "", &options),
+            "dddddd=lll"
+        );
+    }
+
+    #[test]
+    fn emacs() {
+        let options = SlugOptions::default();
+        let title = |s| slugify(s, Some('-'), "", &options);
+        assert_eq!(title("Don't stop me now!"), "dont-stop-me-now");
+        assert_eq!(
+            title("  snake_case -- and (parens)  "),
+            "snake-case-and-parens"
+        );
+        assert_eq!(title("a.b/c"), "abc");
+    }
+
+    #[test]
+    fn options() {
+        let options = SlugOptions {
+            preserve_case: true,
+            extra_excluded: "#".to_string(),
+            transliterate: false,
+            keep_signature_equals: true,
+            normalize: true,
+        };
+        assert_eq!(slugify("Über #Rust", Some('-'), "", &options), "Über-Rust");
+        let options = SlugOptions {
+            transliterate: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            slugify("Crème brûlée", Some('-'), "", &options),
+            "creme-brulee"
+        );
+    }
+
+    #[test]
+    fn normalization() {
+        let options = SlugOptions::default();
+        let nfd = "cafe\u{301}";
+        assert_eq!(slugify(nfd, Some('-'), "", &options), "caf\u{e9}");
+        let options = SlugOptions {
+            normalize: false,
+            ..Default::default()
+        };
+        assert_eq!(slugify(nfd, Some('-'), "", &options), nfd);
     }
 }
@@ -0,0 +1,449 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Write;
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+
+use crate::{Denote, Extension, Identifier, Keywords, Signature, Title};
+
+const TEXT_DELIMITER: &str = "---------------------------";
+
+/// Kind of front matter. Names are the same as in Emacs Denote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrontMatterKind {
+    Org,
+    MarkdownYaml,
+    MarkdownToml,
+    Text,
+}
+
+impl FrontMatterKind {
+    /// Parse front matter kind from its name: "org", "markdown-yaml", "markdown-toml" or "text"
+    pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
+        match string.as_ref().trim() {
+            "org" => Some(Self::Org),
+            "markdown-yaml" | "md-yaml" | "md" => Some(Self::MarkdownYaml),
+            "markdown-toml" | "md-toml" => Some(Self::MarkdownToml),
+            "text" | "txt" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    /// Front matter kind which Emacs Denote uses for files with given extension.
+    /// For markdown files returns `MarkdownYaml`.
+    ///
+    /// ```
+    /// use zeroten_denote::{Extension, FrontMatterKind};
+    ///
+    /// let ext = Extension::new("org").unwrap();
+    /// assert_eq!(FrontMatterKind::from_extension(&ext), Some(FrontMatterKind::Org));
+    /// ```
+    pub fn from_extension(extension: &Extension) -> Option<Self> {
+        match extension.as_str() {
+            "org" => Some(Self::Org),
+            "md" | "markdown" => Some(Self::MarkdownYaml),
+            "txt" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    /// Guess front matter kind by the first line of the file content
+    pub fn detect<S: AsRef<str>>(content: S) -> Option<Self> {
+        let first_line = content.as_ref().lines().next()?.trim_end();
+        if first_line == "---" {
+            Some(Self::MarkdownYaml)
+        } else if first_line == "+++" {
+            Some(Self::MarkdownToml)
+        } else if first_line.starts_with("#+") {
+            Some(Self::Org)
+        } else if first_line.starts_with("title:") {
+            Some(Self::Text)
+        } else {
+            None
+        }
+    }
+
+    /// Extension of the file which usually holds this kind of front matter
+    pub fn extension(self) -> Extension {
+        let ext = match self {
+            Self::Org => "org",
+            Self::MarkdownYaml | Self::MarkdownToml => "md",
+            Self::Text => "txt",
+        };
+        Extension::new(ext).expect("Extension is not empty")
+    }
+
+    /// Lines of front matter without delimiters
+    fn header(self, content: &str) -> Option<Vec<&str>> {
+        let mut lines = content.lines();
+        match self {
+            Self::Org => Some(
+                lines
+                    .take_while(|l| l.starts_with("#+"))
+                    .collect::<Vec<_>>(),
+            ),
+            Self::MarkdownYaml | Self::MarkdownToml => {
+                let delimiter = if self == Self::MarkdownYaml {
+                    "---"
+                } else {
+                    "+++"
+                };
+                if lines.next()?.trim_end() != delimiter {
+                    return None;
+                }
+                let header: Vec<_> = lines
+                    .by_ref()
+                    .take_while(|l| l.trim_end() != delimiter)
+                    .collect();
+                Some(header)
+            }
+            Self::Text => {
+                let header: Vec<_> = lines.take_while(|l| !l.starts_with("---")).collect();
+                Some(header)
+            }
+        }
+    }
+
+    fn key_value(self, line: &str) -> Option<(String, &str)> {
+        let (key, value) = match self {
+            Self::Org => line.strip_prefix("#+")?.split_once(':')?,
+            Self::MarkdownYaml | Self::Text => line.split_once(':')?,
+            Self::MarkdownToml => line.split_once('=')?,
+        };
+        Some((key.trim().to_lowercase(), value.trim()))
+    }
+
+    /// Line with tags as Emacs Denote renders it
+    fn tags_line(self, keywords: &[&str]) -> String {
+        let line = match self {
+            Self::Org if keywords.is_empty() => "#+filetags:".to_string(),
+            Self::Org => format!("#+filetags:   :{}:", keywords.join(":")),
+            Self::MarkdownYaml | Self::MarkdownToml => {
+                let tags: Vec<_> = keywords.iter().map(|k| quote(k)).collect();
+                let tags = format!("[{}]", tags.join(", "));
+                if self == Self::MarkdownYaml {
+                    format!("{:<12}{tags}", "tags:")
+                } else {
+                    format!("{:<10} = {tags}", "tags")
+                }
+            }
+            Self::Text => format!("tags:       {}", keywords.join("  ")),
+        };
+        line.trim_end().to_string()
+    }
+
+    fn parse_tags(self, value: &str) -> Option<Keywords> {
+        let tags: Vec<_> = match self {
+            Self::Org => value.split(':').collect(),
+            Self::MarkdownYaml | Self::MarkdownToml => {
+                let value = value.strip_prefix('[')?.strip_suffix(']')?;
+                value.split(',').map(unquote).collect()
+            }
+            Self::Text => value.split_whitespace().collect(),
+        };
+        Keywords::parse_user_input(tags.join(","))
+    }
+}
+
+/// Front matter of a denote file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FrontMatter {
+    pub title: Option<Title>,
+    pub keywords: Option<Keywords>,
+    pub identifier: Option<Identifier>,
+    pub signature: Option<Signature>,
+}
+
+impl FrontMatter {
+    /// Parse front matter at the beginning of file content. Kind of front matter will be detected
+    /// automatically.
+    ///
+    /// ```
+    /// use zeroten_denote::FrontMatter;
+    ///
+    /// let content = "#+title:      Some title\n#+filetags:   :kw1:kw2:\n\nBody";
+    /// let front_matter = FrontMatter::parse(content).unwrap();
+    /// assert_eq!(front_matter.title.unwrap().to_string(), "--some-title");
+    /// assert_eq!(front_matter.keywords.unwrap().to_string(), "__kw1_kw2");
+    /// ```
+    pub fn parse<S: AsRef<str>>(content: S) -> Option<Self> {
+        fn inner(content: &str) -> Option<FrontMatter> {
+            let kind = FrontMatterKind::detect(content)?;
+            FrontMatter::parse_kind(content, kind)
+        }
+
+        inner(content.as_ref())
+    }
+
+    /// Parse front matter of specified kind at the beginning of file content
+    pub fn parse_kind<S: AsRef<str>>(content: S, kind: FrontMatterKind) -> Option<Self> {
+        fn inner(content: &str, kind: FrontMatterKind) -> Option<FrontMatter> {
+            let mut front_matter = FrontMatter::default();
+            let mut found = false;
+
+            for line in kind.header(content)? {
+                let Some((key, value)) = kind.key_value(line) else {
+                    continue;
+                };
+                match key.as_str() {
+                    "title" => front_matter.title = Title::parse(unquote(value)),
+                    "filetags" | "tags" => front_matter.keywords = kind.parse_tags(value),
+                    "identifier" => {
+                        front_matter.identifier = Identifier::find_in_string(unquote(value));
+                    }
+                    "signature" => front_matter.signature = Signature::parse(unquote(value)),
+                    _ => continue,
+                }
+                found = true;
+            }
+
+            found.then_some(front_matter)
+        }
+
+        inner(content.as_ref(), kind)
+    }
+
+    /// Render front matter in the same form as Emacs Denote does. Ends with an empty line.
+    ///
+    /// ```
+    /// use zeroten_denote::{FrontMatter, FrontMatterKind, Keywords, Title};
+    ///
+    /// let front_matter = FrontMatter {
+    ///     title: Title::parse("Some title"),
+    ///     keywords: Keywords::parse_user_input("kw1,kw2"),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     front_matter.render(FrontMatterKind::Text),
+    ///     "title:      Some title\ntags:       kw1  kw2\n---------------------------\n\n"
+    /// );
+    /// ```
+    pub fn render(&self, kind: FrontMatterKind) -> String {
+        let title = self
+            .title
+            .as_ref()
+            .map(Title::desluggify)
+            .unwrap_or_default();
+        let keywords: Vec<_> = self.keywords.iter().flat_map(Keywords::iter).collect();
+        let identifier = self.identifier.as_ref().map(ToString::to_string);
+        let date = self.identifier.as_ref().map(Identifier::to_datetime);
+        let signature = self.signature.as_ref().map(Signature::as_str);
+
+        let mut lines = Vec::new();
+        match kind {
+            FrontMatterKind::Org => {
+                lines.push(format!("#+title:      {title}"));
+                if let Some(date) = date {
+                    lines.push(format!(
+                        "#+date:       {}",
+                        date.format("[%Y-%m-%d %a %H:%M]")
+                    ));
+                }
+                lines.push(kind.tags_line(&keywords));
+                if let Some(identifier) = identifier {
+                    lines.push(format!("#+identifier: {identifier}"));
+                }
+                if let Some(signature) = signature {
+                    lines.push(format!("#+signature:  {signature}"));
+                }
+            }
+            FrontMatterKind::MarkdownYaml | FrontMatterKind::MarkdownToml => {
+                let yaml = kind == FrontMatterKind::MarkdownYaml;
+                let delimiter = if yaml { "---" } else { "+++" };
+                let field = |key: &str, value: String| {
+                    if yaml {
+                        format!("{:<12}{value}", format!("{key}:"))
+                    } else {
+                        format!("{key:<10} = {value}")
+                    }
+                };
+                lines.push(delimiter.to_string());
+                lines.push(field("title", quote(&title)));
+                if let Some(date) = date {
+                    lines.push(field("date", rfc3339(date)));
+                }
+                lines.push(kind.tags_line(&keywords));
+                if let Some(identifier) = identifier {
+                    lines.push(field("identifier", quote(&identifier)));
+                }
+                if let Some(signature) = signature {
+                    lines.push(field("signature", quote(signature)));
+                }
+                lines.push(delimiter.to_string());
+            }
+            FrontMatterKind::Text => {
+                lines.push(format!("title:      {title}"));
+                if let Some(date) = date {
+                    lines.push(format!("date:       {}", date.format("%Y-%m-%d")));
+                }
+                lines.push(kind.tags_line(&keywords));
+                if let Some(identifier) = identifier {
+                    lines.push(format!("identifier: {identifier}"));
+                }
+                if let Some(signature) = signature {
+                    lines.push(format!("signature:  {signature}"));
+                }
+                lines.push(TEXT_DELIMITER.to_string());
+            }
+        }
+
+        let mut rendered = String::new();
+        for line in lines {
+            // Writing to String can't fail
+            let _ = writeln!(rendered, "{}", line.trim_end());
+        }
+        rendered.push('\n');
+        rendered
+    }
+
+    /// Replace tags in front matter at the beginning of file content. Returns `None` if content
+    /// doesn't have front matter with tags.
+    ///
+    /// ```
+    /// use zeroten_denote::{FrontMatter, Keywords};
+    ///
+    /// let content = "#+title:      Title\n#+filetags:   :mtg:\n\nBody";
+    /// let keywords = Keywords::parse_user_input("meeting").unwrap();
+    /// assert_eq!(
+    ///     FrontMatter::replace_keywords(content, Some(&keywords)).unwrap(),
+    ///     "#+title:      Title\n#+filetags:   :meeting:\n\nBody"
+    /// );
+    /// ```
+    pub fn replace_keywords<S: AsRef<str>>(
+        content: S,
+        keywords: Option<&Keywords>,
+    ) -> Option<String> {
+        fn inner(content: &str, keywords: Option<&Keywords>) -> Option<String> {
+            let kind = FrontMatterKind::detect(content)?;
+            let header = kind.header(content)?;
+            let index = header.iter().position(|line| {
+                kind.key_value(line)
+                    .is_some_and(|(key, _)| key == "filetags" || key == "tags")
+            })?;
+            // Header of Markdown front matter starts after the delimiter
+            let index = match kind {
+                FrontMatterKind::MarkdownYaml | FrontMatterKind::MarkdownToml => index + 1,
+                FrontMatterKind::Org | FrontMatterKind::Text => index,
+            };
+
+            let keywords: Vec<_> = keywords.iter().flat_map(|k| k.iter()).collect();
+            let mut replaced = String::with_capacity(content.len());
+            for (i, line) in content.split_inclusive('\n').enumerate() {
+                if i == index {
+                    replaced.push_str(&kind.tags_line(&keywords));
+                    replaced.push_str(&line[line.trim_end_matches(['\r', '\n']).len()..]);
+                } else {
+                    replaced.push_str(line);
+                }
+            }
+            Some(replaced)
+        }
+
+        inner(content.as_ref(), keywords)
+    }
+}
+
+impl From<&Denote> for FrontMatter {
+    fn from(denote: &Denote) -> Self {
+        Self {
+            title: denote.title.clone(),
+            keywords: denote.keywords.clone(),
+            identifier: Some(denote.identifier.clone()),
+            signature: denote.signature.clone(),
+        }
+    }
+}
+
+fn quote(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn unquote(string: &str) -> &str {
+    let string = string.trim();
+    string
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(string)
+}
+
+fn rfc3339(date: NaiveDateTime) -> String {
+    Local.from_local_datetime(&date).single().map_or_else(
+        || date.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        |d| d.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn denote() -> Denote {
+        let mut denote = Denote::new(Identifier::parse("20240912T13015412").unwrap());
+        denote
+            .title(Title::parse("Some title").unwrap())
+            .keywords(Keywords::parse_user_input("kw1,kw2").unwrap())
+            .signature(Signature::parse("1b").unwrap());
+        denote
+    }
+
+    #[test]
+    fn org() {
+        let rendered = FrontMatter::from(&denote()).render(FrontMatterKind::Org);
+        assert_eq!(
+            rendered,
+            "#+title:      Some title\n\
+             #+date:       [2024-09-12 Thu 13:01]\n\
+             #+filetags:   :kw1:kw2:\n\
+             #+identifier: 20240912T13015412\n\
+             #+signature:  1b\n\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let front_matter = FrontMatter::from(&denote());
+        for kind in [
+            FrontMatterKind::Org,
+            FrontMatterKind::MarkdownYaml,
+            FrontMatterKind::MarkdownToml,
+            FrontMatterKind::Text,
+        ] {
+            let content = format!("{}Body text", front_matter.render(kind));
+            assert_eq!(FrontMatterKind::detect(&content), Some(kind));
+            assert_eq!(FrontMatter::parse(&content).as_ref(), Some(&front_matter));
+        }
+    }
+
+    #[test]
+    fn replace_keywords() {
+        let denote = denote();
+        let keywords = Keywords::parse_user_input("new").unwrap();
+        for kind in [
+            FrontMatterKind::Org,
+            FrontMatterKind::MarkdownYaml,
+            FrontMatterKind::MarkdownToml,
+            FrontMatterKind::Text,
+        ] {
+            let content = format!("{}Body text", FrontMatter::from(&denote).render(kind));
+            let replaced = FrontMatter::replace_keywords(&content, Some(&keywords)).unwrap();
+            let front_matter = FrontMatter::parse(&replaced).unwrap();
+            assert_eq!(front_matter.keywords.as_ref(), Some(&keywords));
+            assert_eq!(front_matter.title, denote.title);
+            assert!(replaced.ends_with("\n\nBody text"));
+
+            let cleared = FrontMatter::replace_keywords(&content, None).unwrap();
+            assert_eq!(FrontMatter::parse(&cleared).unwrap().keywords, None);
+        }
+        assert!(FrontMatter::replace_keywords("Body", Some(&keywords)).is_none());
+    }
+
+    #[test]
+    fn no_front_matter() {
+        assert!(FrontMatter::parse("Just a text").is_none());
+        assert!(FrontMatter::parse("").is_none());
+    }
+}
@@ -6,17 +6,63 @@
 
 use std::fmt::{self, Display};
 use std::fs;
+use std::ops::{Add, Sub};
 use std::path::Path;
+use std::str::FromStr;
 use std::time::SystemTime;
 
-use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 
-use super::regex;
+use crate::{Error, regex};
 
-/// Identifier is a date and time formatted as "20240912T13015412"
-/// and represent unic identifier for file
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Identifier(String);
+/// Precision of identifier time part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Precision {
+    /// Emacs Denote format: "20240912T130154"
+    Seconds,
+    /// Format with centiseconds: "20240912T13015412"
+    #[default]
+    Centiseconds,
+}
+
+impl Precision {
+    /// Parse precision from its name: "seconds" or "centiseconds"
+    pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
+        match string.as_ref().trim() {
+            "seconds" | "s" | "emacs" => Some(Self::Seconds),
+            "centiseconds" | "cs" => Some(Self::Centiseconds),
+            _ => None,
+        }
+    }
+
+    const fn len(self) -> usize {
+        match self {
+            Self::Seconds => 15,
+            Self::Centiseconds => 17,
+        }
+    }
+}
+
+/// Identifier is a date and time formatted as "20240912T13015412" or as "20240912T130154"
+/// (like in Emacs Denote) and represent unic identifier for file.
+///
+/// Identifiers are ordered chronologically.
+///
+/// ```
+/// use chrono::Duration;
+/// use zeroten_denote::Identifier;
+///
+/// let id = Identifier::parse("20240912T13015412").unwrap();
+/// assert_eq!(id.date().to_string(), "2024-09-12");
+/// let later = id.clone() + Duration::days(1);
+/// assert_eq!(later.to_string(), "20240913T13015412");
+/// assert!(id < later);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Identifier {
+    date_time: NaiveDateTime,
+    precision: Precision,
+}
 
 impl Identifier {
     /// Use current system time for create Identifier
@@ -24,14 +70,148 @@ impl Identifier {
         Local::now().naive_local().into()
     }
 
+    /// Create identifier from date and time with given precision
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use zeroten_denote::{Identifier, Precision};
+    ///
+    /// let date_time = NaiveDate::from_ymd_opt(2024, 9, 12)
+    ///     .unwrap()
+    ///     .and_hms_milli_opt(13, 1, 54, 120)
+    ///     .unwrap();
+    /// let id = Identifier::from_datetime(date_time, Precision::Seconds);
+    /// assert_eq!(id.to_string(), "20240912T130154");
+    /// ```
+    pub fn from_datetime(date_time: NaiveDateTime, precision: Precision) -> Self {
+        // Leap second is represented as more than 1_000_000_000 nanoseconds
+        let nanoseconds = date_time.nanosecond().min(999_999_999);
+        let nanoseconds = match precision {
+            Precision::Seconds => 0,
+            Precision::Centiseconds => nanoseconds - nanoseconds % 10_000_000,
+        };
+        let date_time = date_time
+            .with_nanosecond(nanoseconds)
+            .expect("Nanoseconds are less than a second");
+        Self {
+            date_time,
+            precision,
+        }
+    }
+
+    /// Precision of the identifier
+    pub const fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    /// Convert identifier to given precision. Centiseconds are dropped when converting to
+    /// seconds and filled with zeros in the opposite case.
+    ///
+    /// ```
+    /// use zeroten_denote::{Identifier, Precision};
+    ///
+    /// let id = Identifier::parse("20240912T13015412").unwrap();
+    /// let id = id.with_precision(Precision::Seconds);
+    /// assert_eq!(id.to_string(), "20240912T130154");
+    /// assert_eq!(id.with_precision(Precision::Centiseconds).to_string(), "20240912T13015400");
+    /// ```
+    #[must_use]
+    pub fn with_precision(&self, precision: Precision) -> Self {
+        Self::from_datetime(self.date_time, precision)
+    }
+
+    /// Check if identifiers point to the same moment at the coarser of their precisions, so a
+    /// link with seconds precision matches a note with centiseconds identifier
+    ///
+    /// ```
+    /// use zeroten_denote::Identifier;
+    ///
+    /// let id = Identifier::parse("20240912T13015412").unwrap();
+    /// assert!(id.matches(&Identifier::parse("20240912T130154").unwrap()));
+    /// assert!(!id.matches(&Identifier::parse("20240912T13015413").unwrap()));
+    /// ```
+    pub fn matches(&self, other: &Self) -> bool {
+        let precision = self.precision.min(other.precision);
+        self.with_precision(precision) == other.with_precision(precision)
+    }
+
+    /// Date and time which identifier represent
+    pub const fn to_datetime(&self) -> NaiveDateTime {
+        self.date_time
+    }
+
+    /// Date part of identifier
+    pub const fn date(&self) -> NaiveDate {
+        self.date_time.date()
+    }
+
+    /// Time part of identifier
+    pub const fn time(&self) -> NaiveTime {
+        self.date_time.time()
+    }
+
+    /// Add duration to identifier keeping its precision. Returns `None` on overflow.
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        let date_time = self.date_time.checked_add_signed(duration)?;
+        Some(Self::from_datetime(date_time, self.precision))
+    }
+
+    /// Subtract duration from identifier keeping its precision. Returns `None` on overflow.
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        let date_time = self.date_time.checked_sub_signed(duration)?;
+        Some(Self::from_datetime(date_time, self.precision))
+    }
+
+    /// Smallest identifier after this one: one second or one centisecond later depending on
+    /// precision. Useful to find free identifier when the current one is taken.
+    ///
+    /// ```
+    /// use zeroten_denote::Identifier;
+    ///
+    /// let id = Identifier::parse("20240912T13015499").unwrap();
+    /// assert_eq!(id.successor().unwrap().to_string(), "20240912T13015500");
+    /// let id = Identifier::parse("20240912T130159").unwrap();
+    /// assert_eq!(id.successor().unwrap().to_string(), "20240912T130200");
+    /// ```
+    pub fn successor(&self) -> Option<Self> {
+        let step = match self.precision {
+            Precision::Seconds => Duration::seconds(1),
+            Precision::Centiseconds => Duration::milliseconds(10),
+        };
+        self.checked_add(step)
+    }
+
     /// Try parse identifier from given string.
     pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
-        fn inner(string: &str) -> Option<Identifier> {
+        Self::try_parse(string).ok()
+    }
+
+    /// Same as `parse` but returns error which explains why string can't be parsed
+    ///
+    /// ```
+    /// use zeroten_denote::{Error, Identifier};
+    ///
+    /// assert_eq!(
+    ///     Identifier::try_parse("20241399T99999999"),
+    ///     Err(Error::ImpossibleDate("20241399T99999999".to_string()))
+    /// );
+    /// ```
+    pub fn try_parse<S: AsRef<str>>(string: S) -> Result<Self, Error> {
+        fn inner(string: &str) -> Result<Identifier, Error> {
             if string == "now" {
-                return Some(Identifier::now());
+                return Ok(Identifier::now());
+            }
+
+            if let Some(id) = Identifier::find_in_string(string) {
+                return Ok(id);
+            }
+
+            if let Some(id) = regex::IDENTIFIER.find(string) {
+                return Err(Error::ImpossibleDate(id.as_str().to_string()));
             }
 
-            Identifier::find_in_string(string).or_else(|| Identifier::from_string(string))
+            Identifier::from_string(string)
+                .ok_or_else(|| Error::InvalidIdentifier(string.to_string()))
         }
 
         inner(string.as_ref())
@@ -89,7 +269,7 @@ impl Identifier {
         Ok(created.into())
     }
 
-    /// Find identifier in string
+    /// Find identifier in string. Identifiers which represent impossible dates are skipped.
     ///
     /// # Examples
     ///
@@ -99,20 +279,54 @@ impl Identifier {
     /// let string = "some random data 20240912T13015412 asdfsas";
     /// let id = Identifier::find_in_string(string).unwrap();
     /// assert_eq!(id.to_string(), "20240912T13015412");
+    /// assert!(Identifier::find_in_string("20241399T99999999").is_none());
     /// ```
     pub fn find_in_string<S: AsRef<str>>(string: S) -> Option<Self> {
         fn inner(string: &str) -> Option<Identifier> {
-            let captures = regex::IDENTIFIER.captures(string)?;
+            regex::IDENTIFIER
+                .captures_iter(string)
+                .find_map(|captures| {
+                    // We have test in `regex` module to ensure regex is contains `id` group
+                    let id = captures
+                        .name("id")
+                        .expect("Regex: \"id\" name group didn't found");
+                    Identifier::from_schemed(id.as_str())
+                })
+        }
 
-            // We have test in `regex` module to ensure regex is contains `id` group
-            let id = captures
-                .name("id")
-                .expect("Regex: \"id\" name group didn't found");
+        inner(string.as_ref())
+    }
 
-            Some(Identifier(id.as_str().to_string()))
+    /// Parse string which is exactly an identifier. Unlike `try_parse` doesn't accept dates
+    /// and "now".
+    pub(crate) fn parse_exact(string: &str) -> Result<Self, Error> {
+        if !regex::WHOLE_IDENTIFIER.is_match(string) {
+            return Err(Error::InvalidIdentifier(string.to_string()));
         }
+        Self::from_schemed(string).ok_or_else(|| Error::ImpossibleDate(string.to_string()))
+    }
 
-        inner(string.as_ref())
+    /// Parse string which is exactly an identifier
+    fn from_schemed(string: &str) -> Option<Self> {
+        let seconds = string.get(..Precision::Seconds.len())?;
+        let date_time = NaiveDateTime::parse_from_str(seconds, "%Y%m%dT%H%M%S").ok()?;
+        match &string[seconds.len()..] {
+            "" => Some(Self::from_datetime(date_time, Precision::Seconds)),
+            centiseconds => {
+                let centiseconds: i64 = centiseconds.parse().ok()?;
+                let date_time =
+                    date_time.checked_add_signed(Duration::milliseconds(centiseconds * 10))?;
+                Some(Self::from_datetime(date_time, Precision::Centiseconds))
+            }
+        }
+    }
+}
+
+impl FromStr for Identifier {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
     }
 }
 
@@ -124,17 +338,49 @@ impl Default for Identifier {
 
 impl Display for Identifier {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
+        write!(f, "{}", self.date_time.format("%Y%m%dT%H%M%S"))?;
+        if self.precision == Precision::Centiseconds {
+            write!(f, "{:02}", self.date_time.nanosecond() / 10_000_000)?;
+        }
+        Ok(())
+    }
+}
+
+impl Add<Duration> for Identifier {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("`Identifier + Duration` overflowed")
+    }
+}
+
+impl Sub<Duration> for Identifier {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("`Identifier - Duration` overflowed")
+    }
+}
+
+impl Sub for Identifier {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.date_time - rhs.date_time
+    }
+}
+
+impl From<Identifier> for NaiveDateTime {
+    fn from(value: Identifier) -> Self {
+        value.date_time
     }
 }
 
 impl From<NaiveDateTime> for Identifier {
     fn from(value: NaiveDateTime) -> Self {
-        let date = value.date().format("%Y%m%d");
-        let time = value.time();
-        let milliseconds = &time.format("%3f").to_string()[..2];
-        let time = time.format("%H%M%S");
-        Self(format!("{date}T{time}{milliseconds}"))
+        Self::from_datetime(value, Precision::default())
     }
 }
 
@@ -153,7 +399,7 @@ impl From<SystemTime> for Identifier {
 
 #[cfg(test)]
 mod test {
-    use super::Identifier;
+    use super::{Identifier, Precision};
 
     #[test]
     fn find() {
@@ -161,4 +407,19 @@ mod test {
         let identifier = Identifier::parse(text).unwrap();
         assert_eq!(identifier.to_string(), "20240908T19063022");
     }
+
+    #[test]
+    fn skip_impossible_date() {
+        let text = "20241399T99999999 20240908T19063022";
+        let identifier = Identifier::find_in_string(text).unwrap();
+        assert_eq!(identifier.to_string(), "20240908T19063022");
+    }
+
+    #[test]
+    fn find_emacs_identifier() {
+        let text = "some random text 20240908T190630 asd";
+        let identifier = Identifier::parse(text).unwrap();
+        assert_eq!(identifier.to_string(), "20240908T190630");
+        assert_eq!(identifier.precision(), Precision::Seconds);
+    }
 }
@@ -5,8 +5,9 @@
  */
 
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
-use crate::format;
+use crate::{Error, SlugOptions, format};
 
 const PREFIX: &str = "__";
 const SEPARATOR: &str = "_";
@@ -25,7 +26,25 @@ impl Keywords {
     /// );
     /// ```
     pub fn parse_user_input<S: AsRef<str>>(string: S) -> Option<Self> {
-        Self::parse(string, ",")
+        Self::try_parse_user_input(string).ok()
+    }
+
+    /// Same as `parse_user_input` but returns error if no keywords left after slugifying
+    pub fn try_parse_user_input<S: AsRef<str>>(string: S) -> Result<Self, Error> {
+        Self::parse(string, ",", &SlugOptions::default())
+    }
+
+    /// Parse comma separated keywords with given slug options
+    pub fn parse_user_input_with<S: AsRef<str>>(string: S, options: &SlugOptions) -> Option<Self> {
+        Self::try_parse_user_input_with(string, options).ok()
+    }
+
+    /// Same as `parse_user_input_with` but returns error if no keywords left after slugifying
+    pub fn try_parse_user_input_with<S: AsRef<str>>(
+        string: S,
+        options: &SlugOptions,
+    ) -> Result<Self, Error> {
+        Self::parse(string, ",", options)
     }
 
     /// ```
@@ -37,25 +56,165 @@ impl Keywords {
     /// );
     /// ```
     pub fn parse_schemed_string<S: AsRef<str>>(string: S) -> Option<Self> {
-        Self::parse(string, "_")
+        Self::try_parse_schemed_string(string).ok()
+    }
+
+    /// Same as `parse_schemed_string` but returns error if no keywords left after slugifying
+    pub fn try_parse_schemed_string<S: AsRef<str>>(string: S) -> Result<Self, Error> {
+        Self::parse(string, "_", &SlugOptions::default())
+    }
+
+    /// Take already slugified keywords from file name as is
+    pub(crate) fn from_slug(slug: &str) -> Self {
+        Self(
+            slug.split(SEPARATOR)
+                .filter(|k| !k.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+
+    /// Iterate over keywords
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// Number of keywords
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if there are no keywords
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Check if keyword is present. Keyword matches a stored one if they are equal as is or
+    /// after slugifying it.
+    pub fn contains<S: AsRef<str>>(&self, keyword: S) -> bool {
+        self.contains_with(keyword, &SlugOptions::default())
+    }
+
+    /// Same as `contains` but keyword is slugified with given options
+    ///
+    /// ```
+    /// use zeroten_denote::{Denote, SlugOptions};
+    ///
+    /// let denote = Denote::from_path("20240912T130154--note__Work_mtg.txt").unwrap();
+    /// let keywords = denote.keywords.unwrap();
+    /// assert!(keywords.contains("Work"));
+    /// assert!(!keywords.contains("work"));
+    /// let options = SlugOptions {
+    ///     preserve_case: true,
+    ///     ..SlugOptions::default()
+    /// };
+    /// assert!(keywords.contains_with("Work ", &options));
+    /// ```
+    pub fn contains_with<S: AsRef<str>>(&self, keyword: S, options: &SlugOptions) -> bool {
+        let keyword = keyword.as_ref();
+        let slug = Self::slug(keyword, options);
+        self.0.iter().any(|k| k == keyword || *k == slug)
     }
 
-    fn parse<S: AsRef<str>>(string: S, separator: &str) -> Option<Self> {
-        fn inner(string: &str, separator: &str) -> Option<Keywords> {
+    /// Add slugified keyword to the end. Returns `false` if keyword is already present like in
+    /// `contains` or became empty after slugifying.
+    ///
+    /// ```
+    /// use zeroten_denote::Keywords;
+    ///
+    /// let mut keywords = Keywords::parse_user_input("kw1").unwrap();
+    /// assert!(keywords.insert("Kw 2"));
+    /// assert!(!keywords.insert("kw1"));
+    /// assert_eq!(keywords.to_string(), "__kw1_kw2");
+    /// ```
+    pub fn insert<S: AsRef<str>>(&mut self, keyword: S) -> bool {
+        self.insert_with(keyword, &SlugOptions::default())
+    }
+
+    /// Same as `insert` but keyword is slugified with given options
+    pub fn insert_with<S: AsRef<str>>(&mut self, keyword: S, options: &SlugOptions) -> bool {
+        let keyword = keyword.as_ref();
+        let slug = Self::slug(keyword, options);
+        if slug.is_empty() || self.contains_with(keyword, options) {
+            return false;
+        }
+        self.0.push(slug);
+        true
+    }
+
+    /// Remove all occurrences of keyword. Keyword matches like in `contains`. Returns `false`
+    /// if keyword is not present.
+    pub fn remove<S: AsRef<str>>(&mut self, keyword: S) -> bool {
+        self.remove_with(keyword, &SlugOptions::default())
+    }
+
+    /// Same as `remove` but keyword is slugified with given options
+    pub fn remove_with<S: AsRef<str>>(&mut self, keyword: S, options: &SlugOptions) -> bool {
+        let keyword = keyword.as_ref();
+        let slug = Self::slug(keyword, options);
+        let len = self.0.len();
+        self.0.retain(|k| k != keyword && *k != slug);
+        self.0.len() != len
+    }
+
+    /// Remove repeated keywords keeping the first occurrence
+    pub fn dedup(&mut self) {
+        let mut seen = Vec::with_capacity(self.0.len());
+        self.0.retain(|k| {
+            if seen.contains(k) {
+                false
+            } else {
+                seen.push(k.clone());
+                true
+            }
+        });
+    }
+
+    /// Sort keywords alphabetically
+    pub fn sort(&mut self) {
+        self.0.sort();
+    }
+
+    fn slug(keyword: &str, options: &SlugOptions) -> String {
+        format::slugify(keyword, None, "-", options)
+    }
+
+    fn parse<S: AsRef<str>>(
+        string: S,
+        separator: &str,
+        options: &SlugOptions,
+    ) -> Result<Self, Error> {
+        fn inner(string: &str, separator: &str, options: &SlugOptions) -> Result<Keywords, Error> {
             let keywords: Vec<_> = string
                 .split(separator)
-                .map(|s| format::slugify(s, None))
+                .map(|s| format::slugify(s, None, "-", options))
                 .filter(|k| !k.is_empty())
                 .collect();
-            (!keywords.is_empty()).then_some(keywords).map(Keywords)
+            if keywords.is_empty() {
+                return Err(Error::EmptyKeywords);
+            }
+            Ok(Keywords(keywords))
         }
 
-        inner(string.as_ref(), separator)
+        inner(string.as_ref(), separator, options)
+    }
+}
+
+/// Parses comma separated keywords like `Keywords::parse_user_input`
+impl FromStr for Keywords {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse_user_input(s)
     }
 }
 
+/// Empty keywords are displayed as empty string
 impl Display for Keywords {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
         write!(f, "{}{}", PREFIX, self.0.join(SEPARATOR))
     }
 }
@@ -37,21 +37,44 @@
 //!     .to_string();
 //! assert_eq!(denote.to_string(), "20240912T13015412==1b--some-title.txt");
 //! ```
+//!
+//! # Features
+//!
+//! - `serde` implements `Serialize` and `Deserialize` for all name scheme types. `Denote` is
+//!   serialized as a file name string. Use the `structured` module to serialize it as an object
+//!   with separate components.
 
 #![warn(clippy::nursery)]
 
 mod denote;
+mod error;
 mod extension;
 mod format;
+mod front_matter;
 mod identifier;
 mod keywords;
+pub mod links;
+mod order;
+mod query;
 mod regex;
+#[cfg(feature = "serde")]
+mod serialization;
 mod signature;
+mod silo;
 mod title;
 
 pub use denote::Denote;
+pub use error::Error;
 pub use extension::Extension;
-pub use identifier::Identifier;
+pub use format::SlugOptions;
+pub use front_matter::{FrontMatter, FrontMatterKind};
+pub use identifier::{Identifier, Precision};
 pub use keywords::Keywords;
+pub use links::LinkStyle;
+pub use order::{Component, ComponentsOrder};
+pub use query::Query;
+#[cfg(feature = "serde")]
+pub use serialization::structured;
 pub use signature::Signature;
+pub use silo::{IGNORE_FILE, Scan, Silo};
 pub use title::Title;
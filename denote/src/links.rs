@@ -0,0 +1,118 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Links between notes like `[[denote:20240912T130154]]` in Org, `[title](denote:20240912T130154)`
+//! in Markdown or `<denote:20240912T130154>` in plain text.
+//!
+//! ```
+//! use zeroten_denote::{Identifier, links};
+//!
+//! let content = "See [[denote:20240912T130154][other note]] and [this](denote:20240913T08000012).";
+//! let links = links::find(content);
+//! assert_eq!(links[0], Identifier::parse("20240912T130154").unwrap());
+//! assert_eq!(links.len(), 2);
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Extension, Identifier, Scan, regex};
+
+/// Format of a link. Names are the same as in Emacs Denote.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LinkStyle {
+    /// `[[denote:ID][Title]]`
+    #[default]
+    Org,
+    /// `[Title](denote:ID)`
+    Markdown,
+    /// `denote:ID`
+    Text,
+}
+
+impl LinkStyle {
+    /// Parse link style from its name: "org", "markdown" or "text"
+    pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
+        match string.as_ref().trim() {
+            "org" => Some(Self::Org),
+            "markdown" | "md" => Some(Self::Markdown),
+            "text" | "txt" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    /// Link style used in files with given extension
+    pub fn from_extension(extension: &Extension) -> Option<Self> {
+        match extension.as_str() {
+            "org" => Some(Self::Org),
+            "md" | "markdown" => Some(Self::Markdown),
+            "txt" => Some(Self::Text),
+            _ => None,
+        }
+    }
+}
+
+/// Identifiers of linked notes in order of first occurrence without repetitions
+pub fn find<S: AsRef<str>>(content: S) -> Vec<Identifier> {
+    fn inner(content: &str) -> Vec<Identifier> {
+        let mut links: Vec<Identifier> = Vec::new();
+        for captures in regex::LINK.captures_iter(content) {
+            if let Ok(identifier) = Identifier::parse_exact(&captures["id"])
+                && !links.contains(&identifier)
+            {
+                links.push(identifier);
+            }
+        }
+        links
+    }
+
+    inner(content.as_ref())
+}
+
+/// Find linked note in the scanned silo. Identifiers with different precision match if they
+/// point to the same second.
+pub fn resolve<'a>(identifier: &Identifier, scan: &'a Scan) -> Option<&'a Path> {
+    scan.notes
+        .iter()
+        .find(|(_, denote)| denote.identifier.matches(identifier))
+        .map(|(path, _)| path.as_path())
+}
+
+/// Result of backlinks search
+#[derive(Debug, Default)]
+pub struct Backlinks<'a> {
+    /// Notes which link to the note
+    pub notes: Vec<&'a Path>,
+    /// Notes which failed to be read
+    pub errors: Vec<(&'a Path, io::Error)>,
+}
+
+/// Notes in the scanned silo which link to the note with given identifier. Only notes with
+/// extensions of `LinkStyle` are read.
+pub fn backlinks<'a>(identifier: &Identifier, scan: &'a Scan) -> Backlinks<'a> {
+    let mut backlinks = Backlinks::default();
+    for (path, denote) in &scan.notes {
+        let is_text = denote
+            .extension
+            .as_ref()
+            .and_then(LinkStyle::from_extension)
+            .is_some();
+        if !is_text || denote.identifier.matches(identifier) {
+            continue;
+        }
+        match fs::read(path) {
+            Ok(content) => {
+                let links = find(String::from_utf8_lossy(&content));
+                if links.iter().any(|link| link.matches(identifier)) {
+                    backlinks.notes.push(path.as_path());
+                }
+            }
+            Err(err) => backlinks.errors.push((path.as_path(), err)),
+        }
+    }
+    backlinks
+}
@@ -0,0 +1,120 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{self, Display};
+
+/// Component of denote name scheme. Extension is not a component because it's always last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Component {
+    Identifier,
+    Signature,
+    Title,
+    Keywords,
+}
+
+impl Component {
+    /// Parse component from its name: "identifier", "signature", "title" or "keywords"
+    pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
+        match string.as_ref().trim() {
+            "identifier" => Some(Self::Identifier),
+            "signature" => Some(Self::Signature),
+            "title" => Some(Self::Title),
+            "keywords" => Some(Self::Keywords),
+            _ => None,
+        }
+    }
+
+    pub(crate) const fn prefix(self) -> &'static str {
+        match self {
+            Self::Identifier => "@@",
+            Self::Signature => "==",
+            Self::Title => "--",
+            Self::Keywords => "__",
+        }
+    }
+
+    pub(crate) fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "@@" => Some(Self::Identifier),
+            "==" => Some(Self::Signature),
+            "--" => Some(Self::Title),
+            "__" => Some(Self::Keywords),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Identifier => "identifier",
+            Self::Signature => "signature",
+            Self::Title => "title",
+            Self::Keywords => "keywords",
+        };
+        name.fmt(f)
+    }
+}
+
+/// Order of components in file name. Analogue of `denote-file-name-components-order` from
+/// Emacs Denote. Default order is identifier, signature, title, keywords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComponentsOrder([Component; 4]);
+
+impl ComponentsOrder {
+    /// Returns `None` if some component is missed or repeated
+    pub fn new(order: [Component; 4]) -> Option<Self> {
+        let unique = order
+            .iter()
+            .enumerate()
+            .all(|(i, c)| !order[..i].contains(c));
+        unique.then_some(Self(order))
+    }
+
+    /// Parse comma separated list of components
+    ///
+    /// ```
+    /// use zeroten_denote::{Component, ComponentsOrder};
+    ///
+    /// let order = ComponentsOrder::parse("title, identifier, signature, keywords").unwrap();
+    /// assert_eq!(order.iter().next(), Some(Component::Title));
+    /// assert!(ComponentsOrder::parse("title,title,signature,keywords").is_none());
+    /// ```
+    pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
+        fn inner(string: &str) -> Option<ComponentsOrder> {
+            let components: Vec<_> = string
+                .split(',')
+                .map(Component::parse)
+                .collect::<Option<_>>()?;
+            ComponentsOrder::new(components.try_into().ok()?)
+        }
+
+        inner(string.as_ref())
+    }
+
+    /// Iterate over components in order
+    pub fn iter(&self) -> impl Iterator<Item = Component> {
+        self.0.into_iter()
+    }
+}
+
+impl Default for ComponentsOrder {
+    fn default() -> Self {
+        Self([
+            Component::Identifier,
+            Component::Signature,
+            Component::Title,
+            Component::Keywords,
+        ])
+    }
+}
+
+impl Display for ComponentsOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<_> = self.iter().map(|c| c.to_string()).collect();
+        names.join(",").fmt(f)
+    }
+}
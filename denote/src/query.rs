@@ -0,0 +1,209 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::str::FromStr;
+
+use chrono::{Months, NaiveDate};
+use regex::Regex;
+
+use crate::{Denote, Error, Extension};
+
+/// Filter over denote names. All terms of the query must match.
+///
+/// Terms are separated by whitespace:
+///
+/// - `kw:NAME` has keyword, `*` matches any characters
+/// - `date:FROM..TO` identifier date is in the range. Dates are `YYYY`, `YYYY-MM`, `YYYY-QN` or
+///   `YYYY-MM-DD`, both ends are inclusive and either may be omitted. `date:2024-07` matches the
+///   whole month.
+/// - `sig:PATTERN` signature matches pattern, `*` matches any characters
+/// - `title:REGEX` title matches regular expression
+/// - `ext:EXT` has extension, `ext:` has no extension
+/// - any other word is searched in title
+///
+/// Term prefixed with "-" is negated.
+///
+/// ```
+/// use zeroten_denote::{Denote, Query};
+///
+/// let query = Query::parse("kw:meeting date:2024-07..2024-09 sig:1a*").unwrap();
+/// let denote: Denote = "20240912T13015412==1a2--standup__meeting_work.org".parse().unwrap();
+/// assert!(query.matches(&denote));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    terms: Vec<(bool, Term)>,
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Keyword(Regex),
+    Date {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    Signature(Regex),
+    Title(Regex),
+    Extension(Option<String>),
+    Text(String),
+}
+
+impl Query {
+    /// Parse query, see type level docs for syntax
+    pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
+        Self::try_parse(string).ok()
+    }
+
+    /// Same as `parse` but returns error describing invalid term
+    pub fn try_parse<S: AsRef<str>>(string: S) -> Result<Self, Error> {
+        fn inner(string: &str) -> Result<Query, Error> {
+            let terms = string
+                .split_whitespace()
+                .map(|word| match word.strip_prefix('-') {
+                    Some(term) if !term.is_empty() => Ok((true, Term::parse(term)?)),
+                    _ => Ok((false, Term::parse(word)?)),
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Query { terms })
+        }
+
+        inner(string.as_ref())
+    }
+
+    /// Check if denote name satisfies all terms of the query
+    pub fn matches(&self, denote: &Denote) -> bool {
+        self.terms
+            .iter()
+            .all(|(negated, term)| term.matches(denote) != *negated)
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
+    }
+}
+
+impl Term {
+    fn parse(term: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidQuery(term.to_string());
+        let Some((key, value)) = term.split_once(':') else {
+            return Ok(Self::Text(term.to_lowercase()));
+        };
+        match key {
+            "kw" => Ok(Self::Keyword(wildcard(value).ok_or_else(invalid)?)),
+            "sig" => Ok(Self::Signature(wildcard(value).ok_or_else(invalid)?)),
+            "title" => Ok(Self::Title(Regex::new(value).map_err(|_| invalid())?)),
+            "ext" => Ok(Self::Extension(
+                Some(value.trim_start_matches('.'))
+                    .filter(|ext| !ext.is_empty())
+                    .map(ToString::to_string),
+            )),
+            "date" => {
+                let (from, to) = value.split_once("..").unwrap_or((value, value));
+                let bound = |s: &str| {
+                    if s.is_empty() {
+                        Ok(None)
+                    } else {
+                        period(s).map(Some).ok_or_else(invalid)
+                    }
+                };
+                let from = bound(from)?.map(|(start, _)| start);
+                let to = bound(to)?.map(|(_, end)| end);
+                Ok(Self::Date { from, to })
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    fn matches(&self, denote: &Denote) -> bool {
+        match self {
+            Self::Keyword(regex) => denote
+                .keywords
+                .as_ref()
+                .is_some_and(|keywords| keywords.iter().any(|k| regex.is_match(k))),
+            Self::Date { from, to } => {
+                let date = denote.identifier.date();
+                from.is_none_or(|from| from <= date) && to.is_none_or(|to| date <= to)
+            }
+            Self::Signature(regex) => denote
+                .signature
+                .as_ref()
+                .is_some_and(|signature| regex.is_match(signature.as_str())),
+            Self::Title(regex) => denote
+                .title
+                .as_ref()
+                .is_some_and(|title| regex.is_match(title.as_str())),
+            Self::Extension(extension) => {
+                denote.extension.as_ref().map(Extension::as_str) == extension.as_deref()
+            }
+            Self::Text(text) => denote
+                .title
+                .as_ref()
+                .is_some_and(|title| title.as_str().contains(text.as_str())),
+        }
+    }
+}
+
+/// Convert pattern with `*` wildcards in to anchored regex
+fn wildcard(pattern: &str) -> Option<Regex> {
+    if pattern.is_empty() {
+        return None;
+    }
+    let pattern: Vec<_> = pattern.split('*').map(regex::escape).collect();
+    Regex::new(&format!("^{}$", pattern.join(".*"))).ok()
+}
+
+/// First and last day of a year, quarter, month or a single day
+fn period(s: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let mut parts = s.split('-');
+    let year = parts.next()?.parse().ok()?;
+    let (start, months) = match (parts.next(), parts.next(), parts.next()) {
+        (None, ..) => (NaiveDate::from_ymd_opt(year, 1, 1)?, 12),
+        (Some(quarter), None, _) if quarter.starts_with(['Q', 'q']) => {
+            let quarter: u32 = quarter[1..].parse().ok().filter(|q| (1..=4).contains(q))?;
+            (NaiveDate::from_ymd_opt(year, quarter * 3 - 2, 1)?, 3)
+        }
+        (Some(month), None, _) => (NaiveDate::from_ymd_opt(year, month.parse().ok()?, 1)?, 1),
+        (Some(month), Some(day), None) => {
+            let date = NaiveDate::from_ymd_opt(year, month.parse().ok()?, day.parse().ok()?)?;
+            return Some((date, date));
+        }
+        _ => return None,
+    };
+    let end = start.checked_add_months(Months::new(months))?.pred_opt()?;
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use super::period;
+
+    #[test]
+    fn periods() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(period("2024"), Some((date(2024, 1, 1), date(2024, 12, 31))));
+        assert_eq!(
+            period("2024-02"),
+            Some((date(2024, 2, 1), date(2024, 2, 29)))
+        );
+        assert_eq!(
+            period("2024-Q3"),
+            Some((date(2024, 7, 1), date(2024, 9, 30)))
+        );
+        assert_eq!(
+            period("2024-07-15"),
+            Some((date(2024, 7, 15), date(2024, 7, 15)))
+        );
+        assert_eq!(period("2024-Q5"), None);
+        assert_eq!(period("2024-13"), None);
+        assert_eq!(period("July"), None);
+    }
+}
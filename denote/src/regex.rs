@@ -8,8 +8,9 @@ use std::sync::LazyLock;
 
 use regex::Regex;
 
-const ACCEPTABLE_CHARS: &str = r"[\d\p{Alphabetic}]";
-const _IDENTIFIER: &str = r"(?<id>\d{8}T\d{8})";
+/// Anything except whitespace, dot and characters used in component prefixes
+const ACCEPTABLE_CHARS: &str = r"[^\s./\\@=_-]";
+const _IDENTIFIER: &str = r"(?<id>\d{8}T\d{6}(?:\d{2})?)";
 
 pub static IDENTIFIER: LazyLock<Regex> = LazyLock::new(|| Regex::new(_IDENTIFIER).unwrap());
 
@@ -29,19 +30,36 @@ pub fn extension() -> String {
     format!("(?<ext>{ACCEPTABLE_CHARS}+)")
 }
 
-pub static NAME_SCHEME: LazyLock<Regex> = LazyLock::new(|| {
-    let regex = format!(
-        "^{id}(=={signature})?(--{title})?(__{keywords})?(.{ext})?$",
-        id = _IDENTIFIER,
-        signature = signature(),
-        title = title(),
-        keywords = keywords(),
-        ext = extension()
-    );
-    Regex::new(&regex).unwrap()
-});
+fn whole(regex: &str) -> Regex {
+    Regex::new(&format!("^{regex}$")).unwrap()
+}
+
+pub static WHOLE_IDENTIFIER: LazyLock<Regex> = LazyLock::new(|| whole(_IDENTIFIER));
+pub static SIGNATURE: LazyLock<Regex> = LazyLock::new(|| whole(&signature()));
+pub static TITLE: LazyLock<Regex> = LazyLock::new(|| whole(&title()));
+pub static KEYWORDS: LazyLock<Regex> = LazyLock::new(|| whole(&keywords()));
+pub static EXTENSION: LazyLock<Regex> = LazyLock::new(|| whole(&extension()));
+
+/// Identifier at the beginning of file name which is not prefixed with "@@"
+pub static LEADING_IDENTIFIER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!("^{_IDENTIFIER}")).unwrap());
+
+/// Link to a note in Org, Markdown or plain text, e.g. "[[denote:20240912T130154]]"
+pub static LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"denote:{_IDENTIFIER}\b")).unwrap());
+
+/// Prefixes of name scheme components
+pub static DELIMITER: LazyLock<Regex> = LazyLock::new(|| Regex::new("@@|==|--|__").unwrap());
 
 #[test]
 fn regexp() {
-    NAME_SCHEME.captures("test");
+    assert!(WHOLE_IDENTIFIER.is_match("20240912T130154"));
+    assert!(!WHOLE_IDENTIFIER.is_match("test"));
+    assert!(SIGNATURE.is_match("1b=2"));
+    assert!(TITLE.is_match("some-title"));
+    assert!(KEYWORDS.is_match("kw1_kw2"));
+    assert!(EXTENSION.is_match("txt"));
+    assert!(LEADING_IDENTIFIER.is_match("20240912T130154--title"));
+    assert!(LINK.is_match("[[denote:20240912T130154::#heading]]"));
+    assert!(!LINK.is_match("denote:20240912T1301541"));
 }
@@ -0,0 +1,156 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use ::regex::Regex;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Component, Denote, Error, Extension, Identifier, Keywords, Signature, Title, regex};
+
+/// Implement serialization as a string without prefix and deserialization with given parser
+macro_rules! string_serde {
+    ($type:ty, $parse:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let string = String::deserialize(deserializer)?;
+                $parse(string).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+string_serde!(Title, |string| {
+    slug(Component::Title, &regex::TITLE, string).map(Title::from_slug)
+});
+string_serde!(Signature, |string| {
+    slug(Component::Signature, &regex::SIGNATURE, string).map(Signature::from_slug)
+});
+string_serde!(Extension, Extension::try_new);
+
+/// Components are already slugified, so they are taken as is like in file names
+fn slug(component: Component, regex: &Regex, string: String) -> Result<String, Error> {
+    if regex.is_match(&string) {
+        Ok(string)
+    } else {
+        Err(Error::InvalidComponent(component, string))
+    }
+}
+
+impl Serialize for Identifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        Self::parse_exact(&string).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Keywords {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Keywords {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keywords = Vec::<String>::deserialize(deserializer)?;
+        if let Some(keyword) = keywords.iter().find(|k| k.contains('_')) {
+            return Err(D::Error::custom(Error::InvalidComponent(
+                Component::Keywords,
+                keyword.clone(),
+            )));
+        }
+        let keywords = slug(Component::Keywords, &regex::KEYWORDS, keywords.join("_"))
+            .map_err(D::Error::custom)?;
+        Ok(Self::from_slug(&keywords))
+    }
+}
+
+/// Serialized as a file name
+impl Serialize for Denote {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Denote {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(D::Error::custom)
+    }
+}
+
+/// Serialize `Denote` as an object with separate components instead of a file name.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use zeroten_denote::Denote;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Note {
+///     #[serde(with = "zeroten_denote::structured")]
+///     denote: Denote,
+/// }
+/// ```
+pub mod structured {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Denote, Extension, Identifier, Keywords, Signature, Title};
+
+    #[derive(Serialize)]
+    struct Fields<'a> {
+        identifier: &'a Identifier,
+        signature: Option<&'a Signature>,
+        title: Option<&'a Title>,
+        keywords: Option<&'a Keywords>,
+        extension: Option<&'a Extension>,
+    }
+
+    #[derive(Deserialize)]
+    struct OwnedFields {
+        identifier: Identifier,
+        #[serde(default)]
+        signature: Option<Signature>,
+        #[serde(default)]
+        title: Option<Title>,
+        #[serde(default)]
+        keywords: Option<Keywords>,
+        #[serde(default)]
+        extension: Option<Extension>,
+    }
+
+    pub fn serialize<S: Serializer>(denote: &Denote, serializer: S) -> Result<S::Ok, S::Error> {
+        Fields {
+            identifier: &denote.identifier,
+            signature: denote.signature.as_ref(),
+            title: denote.title.as_ref(),
+            keywords: denote.keywords.as_ref(),
+            extension: denote.extension.as_ref(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Denote, D::Error> {
+        let fields = OwnedFields::deserialize(deserializer)?;
+        Ok(Denote {
+            identifier: fields.identifier,
+            signature: fields.signature,
+            title: fields.title,
+            keywords: fields.keywords,
+            extension: fields.extension,
+        })
+    }
+}
@@ -5,8 +5,9 @@
  */
 
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
-use crate::format;
+use crate::{Error, SlugOptions, format};
 
 const PREFIX: &str = "==";
 const SEPARATOR: char = '=';
@@ -22,8 +23,49 @@ impl Signature {
     /// assert_eq!(Signature::parse("1b 2b=3c").unwrap().to_string(), "==1b=2b=3c");
     /// ```
     pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
-        let string = format::slugify(string, Some(SEPARATOR));
-        (!string.is_empty()).then_some(string).map(Self)
+        Self::try_parse(string).ok()
+    }
+
+    /// Same as `parse` but returns error if signature is empty after slugifying
+    pub fn try_parse<S: AsRef<str>>(string: S) -> Result<Self, Error> {
+        Self::try_parse_with(string, &SlugOptions::default())
+    }
+
+    /// Parse signature with given slug options
+    pub fn parse_with<S: AsRef<str>>(string: S, options: &SlugOptions) -> Option<Self> {
+        Self::try_parse_with(string, options).ok()
+    }
+
+    /// Same as `parse_with` but returns error if signature is empty after slugifying
+    pub fn try_parse_with<S: AsRef<str>>(string: S, options: &SlugOptions) -> Result<Self, Error> {
+        let remove = if options.keep_signature_equals {
+            "-"
+        } else {
+            "-="
+        };
+        let string = format::slugify(string, Some(SEPARATOR), remove, options);
+        if string.is_empty() {
+            return Err(Error::EmptySignature);
+        }
+        Ok(Self(string))
+    }
+
+    /// Take already slugified signature from file name as is
+    pub(crate) const fn from_slug(slug: String) -> Self {
+        Self(slug)
+    }
+
+    /// Signature without prefix
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Signature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
     }
 }
 
@@ -0,0 +1,287 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+
+use crate::{Denote, Error, Identifier};
+
+/// Default name of the file with ignore patterns
+pub const IGNORE_FILE: &str = ".denoteignore";
+
+/// Directory with denote files. Named after silos of Emacs Denote.
+///
+/// ```no_run
+/// use zeroten_denote::Silo;
+///
+/// let scan = Silo::new("notes").scan().unwrap();
+/// for (path, denote) in &scan.notes {
+///     println!("{}: {:?}", path.display(), denote.title);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Silo {
+    root: PathBuf,
+    hidden: bool,
+    ignore_file: Option<String>,
+    dirs: bool,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl Silo {
+    /// Create silo which skips hidden files and directories and respects `.denoteignore` file
+    /// in the root
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            hidden: false,
+            ignore_file: Some(IGNORE_FILE.to_string()),
+            dirs: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Root directory of the silo
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Scan hidden files and directories too
+    pub const fn hidden(&mut self, hidden: bool) -> &mut Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Add directories which follow the name scheme to notes. Their content is scanned too.
+    pub const fn dirs(&mut self, dirs: bool) -> &mut Self {
+        self.dirs = dirs;
+        self
+    }
+
+    /// Set name of the file with ignore patterns in the root directory. `None` disables ignore
+    /// file. Each line of the file is a glob. Globs without "/" match file or directory name
+    /// at any depth, others match path relative to the root. Lines starting with "#" are
+    /// comments.
+    pub fn ignore_file(&mut self, name: Option<String>) -> &mut Self {
+        self.ignore_file = name;
+        self
+    }
+
+    /// Scan only files matching at least one of the globs. Globs have the same syntax as lines
+    /// of the ignore file.
+    pub fn include(&mut self, patterns: Vec<String>) -> &mut Self {
+        self.include = patterns;
+        self
+    }
+
+    /// Skip files and directories matching the globs in addition to the ignore file
+    pub fn exclude(&mut self, patterns: Vec<String>) -> &mut Self {
+        self.exclude = patterns;
+        self
+    }
+
+    /// Recursively scan the silo. Fails only if the root or the ignore file can't be read,
+    /// other unreadable directories and entries are listed in `Scan::errors`.
+    pub fn scan(&self) -> io::Result<Scan> {
+        let ignore_file = self.read_ignore_file()?;
+        let ignore = Patterns::parse(
+            ignore_file
+                .lines()
+                .chain(self.exclude.iter().map(String::as_str)),
+        )?;
+        let include = if self.include.is_empty() {
+            None
+        } else {
+            Some(Patterns::parse(self.include.iter().map(String::as_str))?)
+        };
+
+        // Only the root must be readable, errors below it are reported per path
+        let entries = fs::read_dir(&self.root)?;
+        let mut scan = Scan::default();
+        self.scan_entries(&self.root, entries, &ignore, include.as_ref(), &mut scan);
+        scan.notes.sort_by(|a, b| a.0.cmp(&b.0));
+        scan.non_conforming.sort_by(|a, b| a.0.cmp(&b.0));
+        scan.errors.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(scan)
+    }
+
+    fn scan_entries(
+        &self,
+        dir: &Path,
+        entries: fs::ReadDir,
+        ignore: &Patterns,
+        include: Option<&Patterns>,
+        scan: &mut Scan,
+    ) {
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    scan.errors.push((dir.to_path_buf(), err));
+                    break;
+                }
+            };
+            let path = entry.path();
+
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if (hidden && !self.hidden) || ignore.is_match(&self.root, &path) {
+                continue;
+            }
+
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(err) => {
+                    scan.errors.push((path, err));
+                    continue;
+                }
+            };
+
+            if file_type.is_dir() {
+                if self.dirs
+                    && include.is_none_or(|include| include.is_match(&self.root, &path))
+                    && let Ok(denote) = Denote::try_from_dir_path(&path)
+                {
+                    scan.notes.push((path.clone(), denote));
+                }
+                match fs::read_dir(&path) {
+                    Ok(entries) => self.scan_entries(&path, entries, ignore, include, scan),
+                    Err(err) => scan.errors.push((path, err)),
+                }
+            } else if path.is_file() {
+                if include.is_some_and(|include| !include.is_match(&self.root, &path)) {
+                    continue;
+                }
+                match Denote::try_from_path(&path) {
+                    Ok(denote) => scan.notes.push((path, denote)),
+                    Err(err) => scan.non_conforming.push((path, err)),
+                }
+            }
+        }
+    }
+
+    fn read_ignore_file(&self) -> io::Result<String> {
+        let Some(name) = &self.ignore_file else {
+            return Ok(String::new());
+        };
+        match fs::read_to_string(self.root.join(name)) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+            result => result,
+        }
+    }
+}
+
+/// Result of silo scanning
+#[derive(Debug, Default)]
+pub struct Scan {
+    /// Files which follow denote name scheme sorted by path
+    pub notes: Vec<(PathBuf, Denote)>,
+    /// Files which don't follow denote name scheme and the reason
+    pub non_conforming: Vec<(PathBuf, Error)>,
+    /// Directories and entries which can't be read and the reason
+    pub errors: Vec<(PathBuf, io::Error)>,
+}
+
+impl Scan {
+    /// Identifiers which are used by more than one file
+    pub fn duplicates(&self) -> Vec<(&Identifier, Vec<&Path>)> {
+        let mut by_id: BTreeMap<&Identifier, Vec<&Path>> = BTreeMap::new();
+        for (path, denote) in &self.notes {
+            by_id.entry(&denote.identifier).or_default().push(path);
+        }
+        by_id
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .collect()
+    }
+}
+
+impl IntoIterator for Scan {
+    type Item = (PathBuf, Denote);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.notes.into_iter()
+    }
+}
+
+/// Globs from ignore file or from include and exclude options
+#[derive(Debug, Default)]
+struct Patterns {
+    names: GlobSet,
+    paths: GlobSet,
+}
+
+impl Patterns {
+    fn parse<'a>(lines: impl Iterator<Item = &'a str>) -> io::Result<Self> {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+
+        for line in lines.map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let pattern = line.trim_end_matches('/');
+            if let Some(pattern) = pattern
+                .strip_prefix('/')
+                .or_else(|| pattern.contains('/').then_some(pattern))
+            {
+                paths.add(glob(pattern)?);
+            } else {
+                names.add(Glob::new(pattern).map_err(invalid_data)?);
+            }
+        }
+
+        Ok(Self {
+            names: names.build().map_err(invalid_data)?,
+            paths: paths.build().map_err(invalid_data)?,
+        })
+    }
+
+    fn is_match(&self, root: &Path, path: &Path) -> bool {
+        let name_match = path
+            .file_name()
+            .is_some_and(|name| self.names.is_match(name));
+        let path_match = path
+            .strip_prefix(root)
+            .is_ok_and(|relative| self.paths.is_match(relative));
+        name_match || path_match
+    }
+}
+
+fn glob(pattern: &str) -> io::Result<Glob> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(invalid_data)
+}
+
+fn invalid_data(err: globset::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Patterns;
+    use std::path::Path;
+
+    #[test]
+    fn ignore() {
+        let ignore = Patterns::parse("# comment\n*.pdf\n/archive/\nlogs/*.txt\n".lines()).unwrap();
+        let root = Path::new("/notes");
+        assert!(ignore.is_match(root, Path::new("/notes/a/b.pdf")));
+        assert!(ignore.is_match(root, Path::new("/notes/archive")));
+        assert!(!ignore.is_match(root, Path::new("/notes/a/archive")));
+        assert!(ignore.is_match(root, Path::new("/notes/logs/a.txt")));
+        assert!(!ignore.is_match(root, Path::new("/notes/logs/a/b.txt")));
+        assert!(!ignore.is_match(root, Path::new("/notes/a.txt")));
+    }
+}
@@ -5,8 +5,9 @@
  */
 
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
-use crate::format;
+use crate::{Error, SlugOptions, format};
 
 const PREFIX: &str = "--";
 const SEPARATOR: char = '-';
@@ -23,8 +24,41 @@ impl Title {
     /// assert_eq!(Title::parse("some-title").unwrap().to_string(), "--some-title");
     /// ```
     pub fn parse<S: AsRef<str>>(string: S) -> Option<Self> {
-        let string = format::slugify(string, Some(SEPARATOR));
-        (!string.is_empty()).then_some(string).map(Self)
+        Self::try_parse(string).ok()
+    }
+
+    /// Same as `parse` but returns error if title is empty after slugifying
+    pub fn try_parse<S: AsRef<str>>(string: S) -> Result<Self, Error> {
+        Self::try_parse_with(string, &SlugOptions::default())
+    }
+
+    /// Parse title with given slug options
+    ///
+    /// ```
+    /// use zeroten_denote::{SlugOptions, Title};
+    ///
+    /// let options = SlugOptions {
+    ///     preserve_case: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(Title::parse_with("Some Title", &options).unwrap().to_string(), "--Some-Title");
+    /// ```
+    pub fn parse_with<S: AsRef<str>>(string: S, options: &SlugOptions) -> Option<Self> {
+        Self::try_parse_with(string, options).ok()
+    }
+
+    /// Same as `parse_with` but returns error if title is empty after slugifying
+    pub fn try_parse_with<S: AsRef<str>>(string: S, options: &SlugOptions) -> Result<Self, Error> {
+        let string = format::slugify(string, Some(SEPARATOR), "", options);
+        if string.is_empty() {
+            return Err(Error::EmptyTitle);
+        }
+        Ok(Self(string))
+    }
+
+    /// Take already slugified title from file name as is
+    pub(crate) const fn from_slug(slug: String) -> Self {
+        Self(slug)
     }
 
     /// ```
@@ -36,6 +70,19 @@ impl Title {
         let deslugify = self.0.clone().replace(&SEPARATOR.to_string(), " ");
         format::first_letter_uppercase(deslugify).to_string()
     }
+
+    /// Title without prefix
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Title {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s)
+    }
 }
 
 impl Display for Title {
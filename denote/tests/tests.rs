@@ -5,41 +5,183 @@
  */
 
 mod signature {
-    use zeroten_denote::Signature;
+    use zeroten_denote::{Signature, SlugOptions};
 
     #[test]
     fn empty_input() {
         assert!(Signature::parse("").is_none());
     }
+
+    #[test]
+    fn emacs_slug() {
+        let signature = Signature::parse("1a-b+c = 2 _3").unwrap();
+        assert_eq!(signature.to_string(), "==1abc=2=3");
+    }
+
+    #[test]
+    fn without_equals() {
+        let options = SlugOptions {
+            keep_signature_equals: false,
+            ..Default::default()
+        };
+        let signature = Signature::parse_with("1a=2 3", &options).unwrap();
+        assert_eq!(signature.to_string(), "==1a2=3");
+    }
 }
 
 mod title {
-    use zeroten_denote::Title;
+    use zeroten_denote::{Denote, SlugOptions, Title};
 
     #[test]
     fn empty_input() {
         assert!(Title::parse("").is_none());
     }
+
+    #[test]
+    fn emacs_slug() {
+        let title = Title::parse("Hello, World! It's a <test> ü").unwrap();
+        assert_eq!(title.to_string(), "--hello-world-its-a-test-ü");
+    }
+
+    #[test]
+    fn unicode_normalization() {
+        let nfd = Title::parse("Cafe\u{301}").unwrap();
+        let nfc = Title::parse("Caf\u{e9}").unwrap();
+        assert_eq!(nfd, nfc);
+        let nfd = Denote::from_path("20240912T130154--cafe\u{301}.txt").unwrap();
+        let nfc = Denote::from_path("20240912T130154--caf\u{e9}.txt").unwrap();
+        assert_eq!(nfd, nfc);
+    }
+
+    #[test]
+    fn preserve_case_round_trip() {
+        let options = SlugOptions {
+            preserve_case: true,
+            ..Default::default()
+        };
+        let mut denote = Denote::from_path("20240912T130154.txt").unwrap();
+        denote.title(Title::parse_with("Some Title", &options).unwrap());
+        let file_name = denote.to_string();
+        assert_eq!(file_name, "20240912T130154--Some-Title.txt");
+        assert_eq!(Denote::from_path(&file_name), Some(denote));
+    }
 }
 
 mod keywords {
-    use zeroten_denote::Keywords;
+    use zeroten_denote::{Denote, Keywords, SlugOptions};
 
     #[test]
     fn empty_input() {
         assert!(Keywords::parse_user_input("").is_none());
         assert!(Keywords::parse_schemed_string("").is_none());
     }
+
+    #[test]
+    fn emacs_slug() {
+        let keywords = Keywords::parse_user_input("multi-word, snake_case,Re=Mix").unwrap();
+        assert_eq!(keywords.to_string(), "__multiword_snakecase_remix");
+    }
+
+    #[test]
+    fn transliterate() {
+        let options = SlugOptions {
+            transliterate: true,
+            ..Default::default()
+        };
+        let keywords = Keywords::parse_user_input_with("café,北京", &options).unwrap();
+        assert_eq!(keywords.to_string(), "__cafe_beijing");
+    }
+
+    #[test]
+    fn edit() {
+        let mut keywords = Keywords::parse_schemed_string("b_a_c").unwrap();
+        assert!(keywords.contains("A"));
+        assert!(keywords.insert("d"));
+        assert!(!keywords.insert("b"));
+        assert!(!keywords.insert("!"));
+        assert!(keywords.remove("a"));
+        assert!(!keywords.remove("a"));
+        keywords.sort();
+        assert_eq!(keywords.to_string(), "__b_c_d");
+        assert_eq!(keywords.len(), 3);
+
+        let mut keywords = Keywords::parse_schemed_string("a_b_a_c_b").unwrap();
+        keywords.dedup();
+        assert_eq!(keywords.to_string(), "__a_b_c");
+
+        // Keywords from file names keep their case
+        let mut keywords = Denote::from_path("20240912T130154--note__Work_mtg.txt")
+            .unwrap()
+            .keywords
+            .unwrap();
+        assert!(keywords.contains("Work"));
+        assert!(!keywords.insert("Work"));
+        let options = SlugOptions {
+            preserve_case: true,
+            ..SlugOptions::default()
+        };
+        assert!(keywords.insert_with("Idea", &options));
+        assert!(keywords.remove("Work"));
+        assert_eq!(keywords.to_string(), "__mtg_Idea");
+
+        let mut keywords = Keywords::parse_user_input("a").unwrap();
+        keywords.remove("a");
+        assert!(keywords.is_empty());
+        assert_eq!(keywords.to_string(), "");
+    }
 }
 
 mod identifier {
-    use zeroten_denote::Identifier;
+    use chrono::Duration;
+    use zeroten_denote::{Denote, Identifier, Precision};
 
     #[test]
     fn empty_input() {
         let id = Identifier::parse("");
         assert!(id.is_none());
     }
+
+    #[test]
+    fn emacs_file_name() {
+        let denote = Denote::from_path("20240912T130154--some-title__kw.org").unwrap();
+        assert_eq!(denote.identifier.precision(), Precision::Seconds);
+        assert_eq!(denote.to_string(), "20240912T130154--some-title__kw.org");
+    }
+
+    #[test]
+    fn impossible_date() {
+        assert!(Identifier::parse("20241399T99999999").is_none());
+        assert!(Denote::from_path("20240230T130154--title.txt").is_none());
+    }
+
+    #[test]
+    fn chronological_order() {
+        let mut ids: Vec<_> = ["20240912T130154", "20230101T00000000", "20240912T13015301"]
+            .into_iter()
+            .map(|id| Identifier::parse(id).unwrap())
+            .collect();
+        ids.sort();
+        let ids: Vec<_> = ids.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            ids,
+            ["20230101T00000000", "20240912T13015301", "20240912T130154"]
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let id = Identifier::parse("20240912T130154").unwrap();
+        let next = id.clone() + Duration::seconds(1);
+        assert_eq!(next.to_string(), "20240912T130155");
+        assert_eq!(next - id, Duration::seconds(1));
+    }
+
+    #[test]
+    fn convert_precision() {
+        let mut denote = Denote::from_path("20240912T13015412--some-title.org").unwrap();
+        denote.identifier = denote.identifier.with_precision(Precision::Seconds);
+        assert_eq!(denote.to_string(), "20240912T130154--some-title.org");
+    }
 }
 
 mod extension {
@@ -60,6 +202,440 @@ mod extension {
     }
 }
 
+mod error {
+    use std::path::Path;
+
+    use zeroten_denote::{Component, Denote, Error, Extension, Keywords, Signature, Title};
+
+    #[test]
+    fn empty_components() {
+        assert_eq!(Title::try_parse(",,"), Err(Error::EmptyTitle));
+        assert_eq!(Signature::try_parse(" "), Err(Error::EmptySignature));
+        assert_eq!(
+            Keywords::try_parse_user_input(", ,"),
+            Err(Error::EmptyKeywords)
+        );
+        assert_eq!(Extension::try_new("."), Err(Error::EmptyExtension));
+    }
+
+    #[test]
+    fn file_names() {
+        assert_eq!(
+            "20240912T130154--title__kw__kw2".parse::<Denote>(),
+            Err(Error::DuplicateComponent(Component::Keywords))
+        );
+        assert_eq!(
+            "--title__kw.txt".parse::<Denote>(),
+            Err(Error::MissingIdentifier)
+        );
+        assert_eq!(Denote::try_from(Path::new("/")), Err(Error::NoFileName));
+        assert_eq!(
+            "20240912T130154--title.tar.gz".parse::<Denote>(),
+            Err(Error::InvalidExtension("tar.gz".to_string()))
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let title: Title = "Some title".parse().unwrap();
+        assert_eq!(title.to_string(), "--some-title");
+        let keywords: Keywords = "kw1, kw2".parse().unwrap();
+        assert_eq!(keywords.to_string(), "__kw1_kw2");
+    }
+}
+
+mod front_matter {
+    use zeroten_denote::{Denote, FrontMatter, FrontMatterKind};
+
+    #[test]
+    fn from_file_name() {
+        let denote = Denote::from_path("20240912T13015412--some-title__kw1_kw2.md").unwrap();
+        let content = denote.front_matter().unwrap();
+        assert_eq!(
+            FrontMatterKind::detect(&content),
+            Some(FrontMatterKind::MarkdownYaml)
+        );
+        let front_matter = FrontMatter::parse(&content).unwrap();
+        assert_eq!(front_matter, FrontMatter::from(&denote));
+    }
+
+    #[test]
+    fn unknown_extension() {
+        let denote = Denote::from_path("20240912T13015412--some-title.pdf").unwrap();
+        assert!(denote.front_matter().is_none());
+    }
+}
+
+mod order {
+    use zeroten_denote::{ComponentsOrder, Denote};
+
+    #[test]
+    fn round_trip() {
+        let denote = Denote::from_path("20240912T130154==1a--some-title__kw1_kw2.org").unwrap();
+        for order in [
+            "title,identifier,signature,keywords",
+            "signature,title,keywords,identifier",
+            "keywords,signature,identifier,title",
+        ] {
+            let order = ComponentsOrder::parse(order).unwrap();
+            let file_name = denote.to_string_with_order(&order);
+            assert_eq!(Denote::from_path(&file_name).as_ref(), Some(&denote));
+        }
+    }
+
+    #[test]
+    fn identifier_prefix() {
+        let denote = Denote::from_path("--some-title@@20240912T130154.org").unwrap();
+        assert_eq!(denote.to_string(), "20240912T130154--some-title.org");
+        let order = ComponentsOrder::parse("signature,identifier,title,keywords").unwrap();
+        // Signature is empty so identifier is the first
+        assert_eq!(
+            denote.to_string_with_order(&order),
+            "20240912T130154--some-title.org"
+        );
+    }
+
+    #[test]
+    fn wrong_names() {
+        assert!(Denote::from_path("--some-title.org").is_none());
+        assert!(Denote::from_path("--some-title20240912T130154.org").is_none());
+        assert!(Denote::from_path("20240912T130154--a--b.org").is_none());
+        assert!(Denote::from_path("20240912T130154@@20240912T130154.org").is_none());
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use serde::{Deserialize, Serialize};
+    use zeroten_denote::{Denote, Identifier, Keywords, Title};
+
+    const FILE_NAME: &str = "20240912T130154==1b--some-title__kw1_kw2.org";
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        #[serde(with = "zeroten_denote::structured")]
+        denote: Denote,
+    }
+
+    #[test]
+    fn file_name() {
+        let denote = Denote::from_path(FILE_NAME).unwrap();
+        let json = serde_json::to_string(&denote).unwrap();
+        assert_eq!(json, format!("\"{FILE_NAME}\""));
+        let deserialized: Denote = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, denote);
+    }
+
+    #[test]
+    fn structured() {
+        let note = Note {
+            denote: Denote::from_path(FILE_NAME).unwrap(),
+        };
+        let json = serde_json::to_value(&note).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "denote": {
+                    "identifier": "20240912T130154",
+                    "signature": "1b",
+                    "title": "some-title",
+                    "keywords": ["kw1", "kw2"],
+                    "extension": "org",
+                }
+            })
+        );
+        let deserialized: Note = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, note);
+    }
+
+    #[test]
+    fn round_trip() {
+        for name in [
+            FILE_NAME,
+            "20240912T130154--Some-Title__Work.txt",
+            "20240912T130154--c++-notes.txt",
+        ] {
+            let denote = Denote::from_path(name).unwrap();
+            let json = serde_json::to_string(&denote).unwrap();
+            assert_eq!(serde_json::from_str::<Denote>(&json).unwrap(), denote);
+
+            let note = Note { denote };
+            let json = serde_json::to_string(&note).unwrap();
+            assert_eq!(serde_json::from_str::<Note>(&json).unwrap(), note);
+        }
+    }
+
+    #[test]
+    fn components() {
+        let keywords: Keywords = serde_json::from_str(r#"["kw1", "kw2"]"#).unwrap();
+        assert_eq!(keywords.to_string(), "__kw1_kw2");
+        assert!(serde_json::from_str::<Keywords>(r#"["kw_1"]"#).is_err());
+        assert!(serde_json::from_str::<Keywords>("[]").is_err());
+        assert!(serde_json::from_str::<Title>(r#""some title""#).is_err());
+        assert!(serde_json::from_str::<Identifier>(r#""20241399T99999999""#).is_err());
+        assert!(serde_json::from_str::<Identifier>(r#""now""#).is_err());
+        assert!(serde_json::from_str::<Denote>(r#""not a denote name""#).is_err());
+    }
+}
+
+mod silo {
+    use std::fs;
+
+    use zeroten_denote::{Error, Silo};
+
+    fn touch(root: &std::path::Path, path: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(root, "20240912T13015412--a.txt");
+        touch(root, "sub/20240912T13015412--b.txt");
+        touch(root, "sub/20240913T13015412--c.txt");
+        touch(root, "notes.txt");
+        touch(root, ".hidden/20240914T13015412--d.txt");
+        touch(root, "archive/20240915T13015412--e.txt");
+        touch(root, "20240916T13015412--f.pdf");
+        fs::write(root.join(".denoteignore"), "archive/\n*.pdf\n").unwrap();
+
+        let scan = Silo::new(root).scan().unwrap();
+        let names: Vec<_> = scan
+            .notes
+            .iter()
+            .map(|(path, _)| path.strip_prefix(root).unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "20240912T13015412--a.txt",
+                "sub/20240912T13015412--b.txt",
+                "sub/20240913T13015412--c.txt"
+            ]
+        );
+        assert_eq!(scan.non_conforming.len(), 1);
+        assert!(matches!(scan.non_conforming[0].1, Error::NotDenoteName(_)));
+
+        let duplicates = scan.duplicates();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].0.to_string(), "20240912T13015412");
+        assert_eq!(duplicates[0].1.len(), 2);
+
+        let scan = Silo::new(root)
+            .hidden(true)
+            .ignore_file(None)
+            .scan()
+            .unwrap();
+        assert_eq!(scan.notes.len(), 6);
+    }
+
+    #[test]
+    fn include_exclude() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(root, "20240912T13015412--a.txt");
+        touch(root, "20240913T13015412--b.md");
+        touch(root, "sub/20240914T13015412--c.txt");
+        touch(root, "sub/notes.txt");
+        touch(root, "drafts/20240915T13015412--d.txt");
+
+        let scan = Silo::new(root)
+            .include(vec!["*.txt".to_string()])
+            .exclude(vec!["drafts".to_string()])
+            .scan()
+            .unwrap();
+        let names: Vec<_> = scan
+            .notes
+            .iter()
+            .map(|(path, _)| path.strip_prefix(root).unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["20240912T13015412--a.txt", "sub/20240914T13015412--c.txt"]
+        );
+        assert_eq!(scan.non_conforming.len(), 1);
+    }
+
+    #[test]
+    fn dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(
+            root,
+            "20240912T13015412--project__work/20240913T13015412--a.txt",
+        );
+        touch(root, "plain/20240914T13015412--b.txt");
+
+        let scan = Silo::new(root).scan().unwrap();
+        assert_eq!(scan.notes.len(), 2);
+
+        let scan = Silo::new(root).dirs(true).scan().unwrap();
+        assert_eq!(scan.notes.len(), 3);
+        let (path, denote) = &scan.notes[0];
+        assert!(path.is_dir());
+        assert_eq!(denote.extension, None);
+        assert_eq!(denote.to_string(), "20240912T13015412--project__work");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn errors() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(root, "20240912T13015412--a.txt");
+        touch(root, "locked/20240913T13015412--b.txt");
+        let locked = root.join("locked");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Permissions are not checked for root user
+        let readable = fs::read_dir(&locked).is_ok();
+
+        let scan = Silo::new(root).scan();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        let scan = scan.unwrap();
+        if !readable {
+            assert_eq!(scan.notes.len(), 1);
+            assert_eq!(scan.errors.len(), 1);
+            assert_eq!(scan.errors[0].0, locked);
+        }
+
+        assert!(Silo::new(root.join("missing")).scan().is_err());
+    }
+}
+
+mod query {
+    use zeroten_denote::{Denote, Error, Query};
+
+    fn denote(name: &str) -> Denote {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn matches() {
+        let note = denote("20240912T13015412==1a2--client-standup__clientx_meeting.org");
+        let query = |s: &str| Query::parse(s).unwrap().matches(&note);
+
+        assert!(query(""));
+        assert!(query("kw:meeting kw:clientx date:2024-07..2024-09"));
+        assert!(query(
+            "date:2024-Q3 date:2024-09-12 date:..2024 date:2024-09-12.."
+        ));
+        assert!(!query("date:2024-10.."));
+        assert!(query("kw:client* -kw:private"));
+        assert!(!query("kw:client"));
+        assert!(query("sig:1a* -sig:1b*"));
+        assert!(!query("sig:1a"));
+        assert!(query("title:^client-.*up$ standup"));
+        assert!(!query("title:^standup"));
+        assert!(query("ext:org -ext:"));
+        assert!(
+            Query::parse("ext:")
+                .unwrap()
+                .matches(&denote("20240912T13015412--a"))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            Query::try_parse("kw:a date:2024-13").unwrap_err(),
+            Error::InvalidQuery("date:2024-13".to_string())
+        );
+        assert!(Query::parse("title:(").is_none());
+        assert!(Query::parse("sig:").is_none());
+        assert!(Query::parse("unknown:a").is_none());
+    }
+}
+
+mod links {
+    use std::fs;
+
+    use zeroten_denote::{Denote, Extension, Identifier, LinkStyle, Silo, links};
+
+    #[test]
+    fn find() {
+        let content = "[[denote:20240912T130154::#intro][Intro]]\n\
+            [md](denote:20240913T08000012)\n\
+            <denote:20240912T130154> denote:2024 20240914T080000";
+        let ids: Vec<_> = links::find(content)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(ids, ["20240912T130154", "20240913T08000012"]);
+    }
+
+    #[test]
+    fn resolve_and_backlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let a = "20240912T130154--a.org";
+        let b = "20240913T080000--b.md";
+        fs::write(root.join(a), "[[denote:20240913T080000]]").unwrap();
+        fs::write(root.join(b), "[a](denote:20240912T130154)").unwrap();
+        fs::write(
+            root.join("20240914T080000--c.txt"),
+            "denote:20240913T080000",
+        )
+        .unwrap();
+        fs::write(
+            root.join("20240915T080000--d.pdf"),
+            "denote:20240913T080000",
+        )
+        .unwrap();
+
+        let scan = Silo::new(root).scan().unwrap();
+        let id = Identifier::parse("20240913T080000").unwrap();
+        assert_eq!(links::resolve(&id, &scan), Some(root.join(b).as_path()));
+        let backlinks = links::backlinks(&id, &scan);
+        assert_eq!(backlinks.notes.len(), 2);
+        assert_eq!(backlinks.notes[0], root.join(a));
+        assert!(backlinks.errors.is_empty());
+
+        let centiseconds = Identifier::parse("20240913T08000000").unwrap();
+        assert_eq!(
+            links::resolve(&centiseconds, &scan),
+            Some(root.join(b).as_path())
+        );
+        assert_eq!(links::backlinks(&centiseconds, &scan).notes.len(), 2);
+
+        let missing = Identifier::parse("20200101T000000").unwrap();
+        assert_eq!(links::resolve(&missing, &scan), None);
+    }
+
+    #[test]
+    fn render() {
+        let denote: Denote = "20240912T130154--a-title.org".parse().unwrap();
+        let untitled: Denote = "20240912T130154__kw.org".parse().unwrap();
+        assert_eq!(
+            denote.link(LinkStyle::Org, Some("*Intro")),
+            "[[denote:20240912T130154::*Intro][A title]]"
+        );
+        assert_eq!(
+            untitled.link(LinkStyle::Org, None),
+            "[[denote:20240912T130154]]"
+        );
+        assert_eq!(
+            untitled.link(LinkStyle::Markdown, None),
+            "<denote:20240912T130154>"
+        );
+        assert_eq!(denote.link(LinkStyle::Text, None), "denote:20240912T130154");
+        let link = denote.link(LinkStyle::Markdown, Some("intro"));
+        assert_eq!(links::find(link), [denote.identifier]);
+    }
+
+    #[test]
+    fn style() {
+        assert_eq!(LinkStyle::parse("md"), Some(LinkStyle::Markdown));
+        assert_eq!(LinkStyle::parse("html"), None);
+        let ext = Extension::new("txt").unwrap();
+        assert_eq!(LinkStyle::from_extension(&ext), Some(LinkStyle::Text));
+    }
+}
+
 mod denote {
     #[test]
     fn doc() {
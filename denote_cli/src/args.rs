@@ -4,17 +4,59 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-mod rename;
+mod history;
+pub mod keywords;
+mod link;
+mod links;
+pub mod list;
+pub mod rename;
 mod touch;
 
+pub use history::{History, Undo};
+pub use keywords::Keywords;
+pub use link::Link;
+pub use links::{Backlinks, Links};
+pub use list::List;
 pub use rename::Rename;
 pub use touch::Touch;
 
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use denote::{ComponentsOrder, Precision};
 
 #[derive(Parser)]
 #[command(version = clap::crate_version!(), about = clap::crate_description!())]
-pub enum Args {
+pub struct Args {
+    /// Configuration file. Default is ~/.config/denote/config.toml on Linux
+    #[clap(long, global = true)]
+    pub config: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    Backlinks(Backlinks),
+    History(History),
+    Keywords(Keywords),
+    Link(Link),
+    Links(Links),
+    List(List),
     Rename(Rename),
     Touch(Touch),
+    Undo(Undo),
+}
+
+fn parse_precision(string: &str) -> Result<Precision, String> {
+    Precision::parse(string).ok_or_else(|| format!("Unknown identifier precision \"{string}\""))
+}
+
+fn parse_order(string: &str) -> Result<ComponentsOrder, String> {
+    ComponentsOrder::parse(string).ok_or_else(|| {
+        format!(
+            "Wrong components order \"{string}\". \
+            Expected comma separated identifier, signature, title and keywords"
+        )
+    })
 }
@@ -0,0 +1,23 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use clap::Parser;
+
+/// Print journal of renamed and created files
+#[derive(Parser)]
+pub struct History {}
+
+/// Revert renaming or creation of files. Refuses if a file was changed since.
+#[derive(Parser)]
+pub struct Undo {
+    /// Number of latest operations to revert. Stops at the first operation which can't be
+    /// reverted
+    #[clap(long, short, default_value_t = 1, conflicts_with("id"))]
+    pub last: usize,
+    /// Revert operation with given id from history
+    #[clap(long)]
+    pub id: Option<String>,
+}
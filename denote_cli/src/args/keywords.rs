@@ -0,0 +1,73 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Manage keywords of all notes in directory
+#[derive(Parser)]
+pub struct Keywords {
+    #[command(subcommand)]
+    pub command: KeywordsCommand,
+}
+
+#[derive(Subcommand)]
+pub enum KeywordsCommand {
+    List(ListKeywords),
+    Rename(RenameKeyword),
+    Merge(MergeKeywords),
+}
+
+/// List keywords used in file names with number of files. Known keywords from the registry are
+/// listed even if they are not used.
+#[derive(Parser)]
+pub struct ListKeywords {
+    /// Directory with notes
+    #[clap(default_value = ".")]
+    pub dir: PathBuf,
+    /// Sort by
+    #[clap(long, short, value_enum, default_value_t = SortKeywords::Count)]
+    pub sort: SortKeywords,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SortKeywords {
+    /// Most used first
+    Count,
+    /// Alphabetically
+    Name,
+}
+
+/// Rename keyword in file names and front matter
+#[derive(Parser)]
+pub struct RenameKeyword {
+    /// Keyword to rename
+    pub old: String,
+    /// New keyword
+    pub new: String,
+    /// Directory with notes
+    #[clap(default_value = ".")]
+    pub dir: PathBuf,
+    /// Don't ask confirmation to apply changes
+    #[clap(long, short)]
+    pub accept: bool,
+}
+
+/// Replace several keywords with one in file names and front matter, e.g. "merge mtg meet into
+/// meeting"
+#[derive(Parser)]
+pub struct MergeKeywords {
+    /// Keywords to merge followed by "into" and the resulting keyword
+    #[clap(num_args = 3.., value_name = "KEYWORD")]
+    pub keywords: Vec<String>,
+    /// Directory with notes
+    #[clap(long, short, default_value = ".")]
+    pub dir: PathBuf,
+    /// Don't ask confirmation to apply changes
+    #[clap(long, short)]
+    pub accept: bool,
+}
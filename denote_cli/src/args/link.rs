@@ -0,0 +1,27 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use denote::LinkStyle;
+
+/// Print link to the note
+#[derive(Parser)]
+pub struct Link {
+    /// The note
+    pub file: PathBuf,
+    /// Link style: org, markdown or text. By default it's chosen by file extension.
+    #[clap(long, short, value_parser = parse_link_style)]
+    pub style: Option<LinkStyle>,
+    /// Search in the linked note, e.g. a heading
+    #[clap(long)]
+    pub search: Option<String>,
+}
+
+fn parse_link_style(string: &str) -> Result<LinkStyle, String> {
+    LinkStyle::parse(string).ok_or_else(|| format!("Unknown link style \"{string}\""))
+}
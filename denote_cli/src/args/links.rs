@@ -0,0 +1,29 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Print files which are linked from the note
+#[derive(Parser)]
+pub struct Links {
+    /// The note
+    pub file: PathBuf,
+    /// Directory with notes. Directory of the note by default
+    #[clap(long, short)]
+    pub dir: Option<PathBuf>,
+}
+
+/// Print files which link to the note
+#[derive(Parser)]
+pub struct Backlinks {
+    /// The note
+    pub file: PathBuf,
+    /// Directory with notes. Directory of the note by default
+    #[clap(long, short)]
+    pub dir: Option<PathBuf>,
+}
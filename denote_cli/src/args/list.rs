@@ -0,0 +1,55 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+use denote::Query;
+
+/// List denote files in directory and its subdirectories
+#[derive(Parser)]
+pub struct List {
+    /// Directory with notes
+    #[clap(default_value = ".")]
+    pub dir: PathBuf,
+    /// Show only files matching query, e.g. "kw:meeting date:2024-07..2024-09 sig:1a*"
+    #[clap(long, short, value_parser = parse_query)]
+    pub query: Option<Query>,
+    /// Sort by
+    #[clap(long, short, value_enum, default_value_t = Sort::Id)]
+    pub sort: Sort,
+    /// Reverse sort order
+    #[clap(long, short)]
+    pub reverse: bool,
+    /// Output format
+    #[clap(long, short, value_enum, default_value_t = Format::Table)]
+    pub format: Format,
+    /// Include hidden files and directories
+    #[clap(long)]
+    pub hidden: bool,
+    /// Include directories which follow the name scheme
+    #[clap(long)]
+    pub dirs: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Sort {
+    Id,
+    Title,
+    Signature,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Tsv,
+}
+
+fn parse_query(string: &str) -> Result<Query, String> {
+    Query::try_parse(string).map_err(|err| err.to_string())
+}
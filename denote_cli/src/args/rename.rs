@@ -4,24 +4,49 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use denote::{ComponentsOrder, Precision};
+
+use crate::collision::OnCollision;
 
 use std::path::PathBuf;
 
 /// Rename existing file
 #[derive(Parser)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Rename {
     #[clap(required = true)]
     pub paths: Vec<PathBuf>,
+    /// Rename files in the given directories and their subdirectories.
+    /// Hidden files and files from .denoteignore are skipped
+    #[clap(long, short)]
+    pub recursive: bool,
+    /// Rename only files matching the glob. Globs without "/" match file name,
+    /// others match path relative to the directory. Can be repeated
+    #[clap(long, requires = "recursive")]
+    pub include: Vec<String>,
+    /// Skip files and directories matching the glob. Can be repeated
+    #[clap(long, requires = "recursive")]
+    pub exclude: Vec<String>,
+    /// Skip files which already follow the name scheme
+    #[clap(long)]
+    pub skip_conforming: bool,
     /// The date to be used as the identifier.
     /// Can be used a date like 2022-06-30 or a date and time like 2022-06-16 14:30.
     /// Can be used "now" to use current system date and time.
-    /// Can be used schemed format like 20240903T21133121.
+    /// Can be used schemed format like 20240903T21133121 or 20240903T211331.
     #[clap(long, short, conflicts_with("date_from_metadata"))]
     pub date: Option<String>,
     /// Use creation file date from the file metadata
     #[clap(long, short = 'm', conflicts_with("date"))]
     pub date_from_metadata: bool,
+    /// Identifier precision: "seconds" (like in Emacs Denote) or "centiseconds".
+    /// If not specified here or in the config, rename keeps precision of existing identifier.
+    #[clap(long, short, value_parser = super::parse_precision)]
+    pub precision: Option<Precision>,
+    /// Order of file name components, e.g. "title,identifier,signature,keywords"
+    #[clap(long, short = 'O', value_parser = super::parse_order, default_value_t)]
+    pub order: ComponentsOrder,
     /// Specify the signature
     #[clap(long, short)]
     pub signature: Option<String>,
@@ -29,9 +54,18 @@ pub struct Rename {
     #[clap(long, short)]
     pub title: Option<String>,
     /// Specify the keywords
-    #[clap(long, short)]
+    #[clap(long, short, conflicts_with("clear_keywords"))]
     pub keywords: Option<String>,
-    /// Specify the v
+    /// Add keyword to existing ones. Can be repeated or comma separated
+    #[clap(long, value_delimiter = ',')]
+    pub add_keyword: Vec<String>,
+    /// Remove keyword from existing ones. Can be repeated or comma separated
+    #[clap(long, value_delimiter = ',')]
+    pub remove_keyword: Vec<String>,
+    /// Remove all keywords
+    #[clap(long)]
+    pub clear_keywords: bool,
+    /// Specify the extension
     #[clap(long, short)]
     pub extension: Option<String>,
     /// Don't ask anything. Use default values if not specified
@@ -40,4 +74,27 @@ pub struct Rename {
     /// Don't ask confirmation to rename file
     #[clap(long, short)]
     pub accept: bool,
+    /// Ask confirmation even if it's disabled in the config
+    #[clap(long, conflicts_with("accept"))]
+    pub confirm: bool,
+    /// Rename the rest of files if some of them fail. By default nothing is renamed then
+    #[clap(long)]
+    pub partial: bool,
+    /// What to do if the new name is taken by another file.
+    /// Ask by default, refuse if --non-interactive
+    #[clap(long, value_enum)]
+    pub on_collision: Option<OnCollision>,
+    /// Print new names, collisions and unchanged files without renaming anything.
+    /// Implies --non-interactive
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Format of the dry run output
+    #[clap(long, value_enum, default_value_t = PlanFormat::Text, requires = "dry_run")]
+    pub format: PlanFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PlanFormat {
+    Text,
+    Json,
 }
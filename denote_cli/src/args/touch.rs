@@ -4,25 +4,45 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::path::PathBuf;
+
 use clap::Parser;
+use denote::{ComponentsOrder, FrontMatterKind, Precision};
+
+use crate::collision::OnCollision;
 
 /// Create new file
 #[derive(Parser)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Touch {
     /// Specify the title
     pub title: Option<String>,
     /// The date to be used as the identifier.
     /// Can be used a date like 2022-06-30 or a date and time like 2022-06-16 14:30.
     /// Can be used "now" to use current system date and time.
-    /// Can be used schemed format like 20240903T21133121.
+    /// Can be used schemed format like 20240903T21133121 or 20240903T211331.
     #[clap(long, short, default_value = "now")]
     pub date: String,
+    /// Identifier precision: "seconds" (like in Emacs Denote) or "centiseconds" (default)
+    #[clap(long, short, value_parser = super::parse_precision)]
+    pub precision: Option<Precision>,
+    /// Order of file name components, e.g. "title,identifier,signature,keywords"
+    #[clap(long, short = 'O', value_parser = super::parse_order, default_value_t)]
+    pub order: ComponentsOrder,
     /// Specify the signature
     #[clap(long, short)]
     pub signature: Option<String>,
     /// Specify the keywords
     #[clap(long, short)]
     pub keywords: Option<String>,
+    /// Directory to create the file in.
+    /// Default is notes-dir from the config or the current directory
+    #[clap(long)]
+    pub dir: Option<PathBuf>,
+    /// Subdirectory of --dir built from the identifier date, e.g. "%Y/%m".
+    /// Missing directories are created
+    #[clap(long)]
+    pub subdir: Option<String>,
     /// Specify the file extension
     #[clap(long, short)]
     pub extension: Option<String>,
@@ -32,7 +52,25 @@ pub struct Touch {
     /// Don't ask confirmation to create file
     #[clap(long, short)]
     pub accept: bool,
+    /// Ask confirmation even if it's disabled in the config
+    #[clap(long, conflicts_with("accept"))]
+    pub confirm: bool,
+    /// What to do if a file with the same name exists.
+    /// Ask by default, refuse if --non-interactive
+    #[clap(long, value_enum)]
+    pub on_collision: Option<OnCollision>,
     /// Open created file in default text editor
     #[clap(long, short)]
     pub open: bool,
+    /// Front matter type: org, markdown-yaml, markdown-toml or text.
+    /// By default it's chosen by file extension.
+    #[clap(long, short, value_parser = parse_front_matter_kind)]
+    pub front_matter: Option<FrontMatterKind>,
+    /// Don't write front matter to created file
+    #[clap(long, conflicts_with("front_matter"))]
+    pub no_front_matter: bool,
+}
+
+fn parse_front_matter_kind(string: &str) -> Result<FrontMatterKind, String> {
+    FrontMatterKind::parse(string).ok_or_else(|| format!("Unknown front matter type \"{string}\""))
 }
@@ -0,0 +1,136 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use denote::Denote;
+
+use crate::ui::UI;
+
+/// What to do when the new file name is already taken
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OnCollision {
+    /// Ask what to do
+    Ask,
+    /// Stop with an error
+    Refuse,
+    /// Replace existing file
+    Overwrite,
+    /// Leave the file as is
+    Skip,
+    /// Increase identifier until the name is free
+    Bump,
+}
+
+/// Target path after resolving collision
+pub enum Target {
+    /// Path is free
+    Free(PathBuf),
+    /// Path is taken and existing file should be replaced
+    Overwrite(PathBuf),
+    Skip,
+}
+
+/// Find target path for the name scheme. `taken` checks if a path is occupied, usually with
+/// `is_taken`. Identifier of the name scheme is changed on bump.
+pub fn resolve(
+    name_scheme: &mut Denote,
+    taken: impl Fn(&Path) -> bool,
+    path_of: impl Fn(&Denote) -> Result<PathBuf>,
+    policy: OnCollision,
+    ui: &mut UI,
+) -> Result<Target> {
+    let mut target = path_of(name_scheme)?;
+    if !taken(&target) {
+        return Ok(Target::Free(target));
+    }
+
+    let policy = match policy {
+        OnCollision::Ask => ui.collision(&target)?,
+        policy => policy,
+    };
+    match policy {
+        OnCollision::Ask | OnCollision::Refuse => {
+            bail!("File \"{}\" already exists", target.display())
+        }
+        OnCollision::Overwrite => Ok(Target::Overwrite(target)),
+        OnCollision::Skip => Ok(Target::Skip),
+        OnCollision::Bump => {
+            while taken(&target) {
+                name_scheme.identifier = name_scheme
+                    .identifier
+                    .successor()
+                    .context("Failed to find free identifier")?;
+                target = path_of(name_scheme)?;
+            }
+            Ok(Target::Free(target))
+        }
+    }
+}
+
+/// Check if `new` exists and is not the same file as `old`, e.g. on case insensitive file system
+pub fn is_taken(old: Option<&Path>, new: &Path) -> bool {
+    if new.symlink_metadata().is_err() {
+        return false;
+    }
+    let Some(old) = old else {
+        return true;
+    };
+    match (old.canonicalize(), new.canonicalize()) {
+        (Ok(old), Ok(new)) => old != new,
+        _ => true,
+    }
+}
+
+/// Rename file failing with `AlreadyExists` if the target exists. On Linux it's atomic, so a
+/// file created by another process in the meantime is never replaced.
+pub fn rename_no_replace(from: &Path, to: &Path) -> io::Result<()> {
+    // Same file under another name, e.g. changing case on case insensitive file system
+    if to.symlink_metadata().is_ok() && !is_taken(Some(from), to) {
+        return std::fs::rename(from, to);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = |path: &Path| {
+            CString::new(path.as_os_str().as_bytes())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+        };
+        let (c_from, c_to) = (c_path(from)?, c_path(to)?);
+        // SAFETY: both paths are valid nul-terminated strings
+        let result = unsafe {
+            libc::renameat2(
+                libc::AT_FDCWD,
+                c_from.as_ptr(),
+                libc::AT_FDCWD,
+                c_to.as_ptr(),
+                libc::RENAME_NOREPLACE,
+            )
+        };
+        if result == 0 {
+            return Ok(());
+        }
+        let err = io::Error::last_os_error();
+        // File system doesn't support the flag, fall back to the check below
+        if !matches!(err.raw_os_error(), Some(libc::EINVAL | libc::ENOSYS)) {
+            return Err(err);
+        }
+    }
+
+    if to.symlink_metadata().is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("\"{}\" already exists", to.display()),
+        ));
+    }
+    std::fs::rename(from, to)
+}
@@ -0,0 +1,154 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Configuration file with defaults for command line options. Options given on the command line
+//! take precedence.
+//!
+//! ```toml
+//! notes-dir = "~/notes"
+//! subdir = "%Y/%m"
+//! extension = "org"
+//! front-matter = "org"
+//! precision = "seconds"
+//! known-keywords = ["work", "idea"]
+//! confirm = false
+//!
+//! [slug]
+//! transliterate = true
+//! ```
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use denote::{FrontMatterKind, Precision, SlugOptions};
+use serde::{Deserialize, Deserializer};
+
+use crate::args;
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// Directory where new notes are created
+    pub notes_dir: Option<PathBuf>,
+    /// Subdirectory of the notes directory built from the identifier date, e.g. "%Y/%m"
+    pub subdir: Option<String>,
+    /// Extension of new notes
+    pub extension: Option<String>,
+    /// Front matter type of new notes
+    #[serde(deserialize_with = "front_matter")]
+    pub front_matter: Option<FrontMatterKind>,
+    /// Identifier precision of new and renamed notes
+    #[serde(deserialize_with = "precision")]
+    pub precision: Option<Precision>,
+    /// Known keywords. Replaces the keyword registry file.
+    pub known_keywords: Option<Vec<String>>,
+    /// Ask confirmation to create or rename files
+    pub confirm: Option<bool>,
+    pub slug: Slug,
+}
+
+/// Slug options, not specified ones are the same as in Emacs Denote
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Slug {
+    preserve_case: Option<bool>,
+    extra_excluded: Option<String>,
+    transliterate: Option<bool>,
+    keep_signature_equals: Option<bool>,
+    normalize: Option<bool>,
+}
+
+impl Config {
+    /// Default location: `~/.config/denote/config.toml` on Linux
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("denote").join("config.toml"))
+    }
+
+    /// Read config from `path` or from the default location. Missing file at the default
+    /// location gives default config.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !required => {
+                return Ok(Self::default());
+            }
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read \"{}\"", path.display()));
+            }
+        };
+        let mut config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse \"{}\"", path.display()))?;
+        config.notes_dir = config.notes_dir.map(expand_home);
+        Ok(config)
+    }
+
+    pub fn slug_options(&self) -> SlugOptions {
+        let default = SlugOptions::default();
+        let slug = &self.slug;
+        SlugOptions {
+            preserve_case: slug.preserve_case.unwrap_or(default.preserve_case),
+            extra_excluded: slug
+                .extra_excluded
+                .clone()
+                .unwrap_or(default.extra_excluded),
+            transliterate: slug.transliterate.unwrap_or(default.transliterate),
+            keep_signature_equals: slug
+                .keep_signature_equals
+                .unwrap_or(default.keep_signature_equals),
+            normalize: slug.normalize.unwrap_or(default.normalize),
+        }
+    }
+
+    /// Fill options which are not given on the command line
+    pub fn merge_touch(&self, args: &mut args::Touch) {
+        args.dir = args.dir.take().or_else(|| self.notes_dir.clone());
+        args.subdir = args.subdir.take().or_else(|| self.subdir.clone());
+        args.extension = args.extension.take().or_else(|| self.extension.clone());
+        args.precision = args.precision.or(self.precision);
+        if !args.no_front_matter {
+            args.front_matter = args.front_matter.or(self.front_matter);
+        }
+        args.accept |= !args.confirm && self.confirm == Some(false);
+    }
+
+    /// Fill options which are not given on the command line
+    pub fn merge_rename(&self, args: &mut args::Rename) {
+        args.precision = args.precision.or(self.precision);
+        args.accept |= !args.confirm && self.confirm == Some(false);
+    }
+}
+
+/// Replace leading "~" with home directory
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(relative), Some(home)) => home.join(relative),
+        _ => path,
+    }
+}
+
+fn front_matter<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<FrontMatterKind>, D::Error> {
+    let string = String::deserialize(deserializer)?;
+    FrontMatterKind::parse(&string)
+        .map(Some)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown front matter type \"{string}\"")))
+}
+
+fn precision<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Precision>, D::Error> {
+    let string = String::deserialize(deserializer)?;
+    Precision::parse(&string).map(Some).ok_or_else(|| {
+        serde::de::Error::custom(format!("unknown identifier precision \"{string}\""))
+    })
+}
@@ -0,0 +1,109 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use anyhow::{Result, bail};
+
+use crate::args;
+use crate::journal::{Action, Journal};
+use crate::ui::UI;
+
+pub fn history(_args: &args::History) -> Result<()> {
+    let journal = Journal::open()?;
+    let entries = journal.entries()?;
+    let undone = Journal::undone(&entries);
+
+    for entry in &entries {
+        let mark = if undone.contains(entry.id.as_str()) {
+            " (undone)"
+        } else {
+            ""
+        };
+        let description = match &entry.action {
+            Action::Rename { old, new, .. } => {
+                format!("rename {} -> {}", old.display(), new.display())
+            }
+            Action::Create { path, .. } => format!("create {}", path.display()),
+            Action::Batch { renames } => {
+                let lines: Vec<_> = renames
+                    .iter()
+                    .map(|renamed| {
+                        let front_matter = if renamed.tags.is_some() {
+                            " (front matter)"
+                        } else {
+                            ""
+                        };
+                        format!(
+                            "    {} -> {}{front_matter}",
+                            renamed.old.display(),
+                            renamed.new.display()
+                        )
+                    })
+                    .collect();
+                format!("rename {} files\n{}", renames.len(), lines.join("\n"))
+            }
+            Action::Undo { undone } => format!("undo {undone}"),
+        };
+        println!("{}  {}  {description}{mark}", entry.id, entry.time);
+    }
+
+    Ok(())
+}
+
+pub fn undo(args: &args::Undo) -> Result<()> {
+    let journal = Journal::open()?;
+    let entries = journal.entries()?;
+    let undone = Journal::undone(&entries);
+    let mut candidates = entries
+        .iter()
+        .rev()
+        .filter(|entry| !matches!(entry.action, Action::Undo { .. }))
+        .filter(|entry| !undone.contains(entry.id.as_str()));
+
+    let targets: Vec<_> = if let Some(id) = &args.id {
+        if undone.contains(id.as_str()) {
+            bail!("Operation {id} is already undone");
+        }
+        match candidates.find(|entry| &entry.id == id) {
+            Some(entry) => vec![entry],
+            None => bail!("Operation {id} is not found"),
+        }
+    } else {
+        candidates.take(args.last).collect()
+    };
+
+    if targets.is_empty() {
+        UI::no_action_needed();
+        return Ok(());
+    }
+
+    let total = targets.len();
+    for (done, entry) in targets.into_iter().enumerate() {
+        if let Err(err) = journal.undo(entry) {
+            if total > 1 {
+                println!("Undone {done} of {total} operations");
+            }
+            return Err(err);
+        }
+        match &entry.action {
+            Action::Rename { old, new, .. } => {
+                println!("Renamed {} back to {}", new.display(), old.display());
+            }
+            Action::Create { path, .. } => println!("Removed {}", path.display()),
+            Action::Batch { renames } => {
+                for renamed in renames {
+                    println!(
+                        "Renamed {} back to {}",
+                        renamed.new.display(),
+                        renamed.old.display()
+                    );
+                }
+            }
+            Action::Undo { .. } => {}
+        }
+    }
+
+    Ok(())
+}
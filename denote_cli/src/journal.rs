@@ -0,0 +1,347 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Append-only journal of renamed and created files. Each line is a JSON object.
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::rename_no_replace;
+use crate::transaction::{self, Move};
+use crate::ui::UI;
+
+#[derive(Serialize, Deserialize)]
+pub struct Entry {
+    /// Microseconds since Unix epoch and process id, unique without reading the journal
+    pub id: String,
+    pub time: String,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum Action {
+    Rename {
+        old: PathBuf,
+        new: PathBuf,
+        stamp: Stamp,
+    },
+    Create {
+        path: PathBuf,
+        stamp: Stamp,
+    },
+    /// Several files renamed at once, undone as a whole
+    Batch {
+        renames: Vec<Renamed>,
+    },
+    /// Operation with given id was undone
+    Undo {
+        undone: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Renamed {
+    pub old: PathBuf,
+    pub new: PathBuf,
+    pub stamp: Stamp,
+    /// Front matter tags rewritten together with the rename
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Tags>,
+}
+
+impl Renamed {
+    fn new(old: &Path, new: &Path, tags: Option<Tags>) -> Result<Self> {
+        let new = new.canonicalize()?;
+        let old = new.with_file_name(old.file_name().context("Path doesn't have file name")?);
+        let stamp = Stamp::new(&new)?;
+        Ok(Self {
+            old,
+            new,
+            stamp,
+            tags,
+        })
+    }
+}
+
+/// Tags line of front matter before and after the operation
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Tags {
+    pub old: String,
+    pub new: String,
+}
+
+impl Tags {
+    /// First line which differs between old and new content
+    pub fn diff(old: &str, new: &str) -> Option<Self> {
+        old.lines()
+            .zip(new.lines())
+            .find(|(old, new)| old != new)
+            .map(|(old, new)| Self {
+                old: old.to_string(),
+                new: new.to_string(),
+            })
+    }
+
+    /// Content with the new tags line replaced by the old one. Returns `None` if there is no
+    /// new tags line.
+    fn restore(&self, content: &str) -> Option<String> {
+        let mut restored = false;
+        let lines: Vec<_> = content
+            .split_inclusive('\n')
+            .map(|line| {
+                let text = line.trim_end_matches(['\r', '\n']);
+                if !restored && text == self.new {
+                    restored = true;
+                    format!("{}{}", self.old, &line[text.len()..])
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect();
+        restored.then(|| lines.concat())
+    }
+}
+
+/// Size and modification time used to detect changes of a file after the operation
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+pub struct Stamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl Stamp {
+    fn new(path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    /// Journal in the state directory: `~/.local/state/denote/journal.jsonl` on Linux
+    pub fn open() -> Result<Self> {
+        let dir = dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .context("Failed to find state directory")?;
+        Ok(Self {
+            path: dir.join("denote").join("journal.jsonl"),
+        })
+    }
+
+    /// Record applied moves. Several moves are recorded as one batch.
+    pub fn record_renames(&self, moves: &[Move]) -> Result<()> {
+        self.record_edits(moves, &[])
+    }
+
+    /// Record applied moves with front matter tags rewritten in the renamed files. Tags are
+    /// matched to moves by index. Moves with tags are always recorded as a batch.
+    pub fn record_edits(&self, moves: &[Move], tags: &[Option<Tags>]) -> Result<()> {
+        let mut renames = moves
+            .iter()
+            .enumerate()
+            .map(|(i, m)| Renamed::new(&m.from, &m.to, tags.get(i).cloned().flatten()))
+            .collect::<Result<Vec<_>>>()?;
+        if renames.len() == 1
+            && renames[0].tags.is_none()
+            && let Some(Renamed {
+                old, new, stamp, ..
+            }) = renames.pop()
+        {
+            return self.append(Action::Rename { old, new, stamp });
+        }
+        self.append(Action::Batch { renames })
+    }
+
+    pub fn record_create(&self, path: &Path) -> Result<()> {
+        let path = path.canonicalize()?;
+        let stamp = Stamp::new(&path)?;
+        self.append(Action::Create { path, stamp })
+    }
+
+    /// Entries of the journal. Corrupted lines are skipped with a warning.
+    pub fn entries(&self) -> Result<Vec<Entry>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read \"{}\"", self.path.display()));
+            }
+        };
+        let mut entries = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(err) => UI::warning(format!(
+                    "skipping corrupted line {} of \"{}\": {err}",
+                    i + 1,
+                    self.path.display()
+                )),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Ids of operations which were undone
+    pub fn undone(entries: &[Entry]) -> HashSet<&str> {
+        entries
+            .iter()
+            .filter_map(|entry| match &entry.action {
+                Action::Undo { undone } => Some(undone.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Revert the operation. Fails if the file was changed or moved after the operation.
+    /// Rewritten front matter tags are restored together with file names.
+    pub fn undo(&self, entry: &Entry) -> Result<()> {
+        match &entry.action {
+            Action::Rename { old, new, stamp } => {
+                check_unchanged(&entry.id, new, stamp)?;
+                rename_no_replace(new, old).with_context(|| {
+                    format!(
+                        "Can't undo operation {}: failed to rename \"{}\" to \"{}\"",
+                        entry.id,
+                        new.display(),
+                        old.display()
+                    )
+                })?;
+            }
+            Action::Create { path, stamp } => {
+                check_unchanged(&entry.id, path, stamp)?;
+                fs::remove_file(path)?;
+            }
+            Action::Batch { renames } => {
+                for renamed in renames {
+                    check_unchanged(&entry.id, &renamed.new, &renamed.stamp)?;
+                }
+                let mut contents = Vec::new();
+                for renamed in renames {
+                    let Some(tags) = &renamed.tags else {
+                        continue;
+                    };
+                    let content = fs::read_to_string(&renamed.new)?;
+                    let Some(restored) = tags.restore(&content) else {
+                        bail!(
+                            "Can't undo operation {}: front matter of \"{}\" was changed since",
+                            entry.id,
+                            renamed.new.display()
+                        );
+                    };
+                    contents.push((renamed.old.clone(), restored));
+                }
+                let moves: Vec<_> = renames
+                    .iter()
+                    .map(|renamed| Move {
+                        from: renamed.new.clone(),
+                        to: renamed.old.clone(),
+                        overwrite: false,
+                    })
+                    .collect();
+                transaction::apply_with_contents(&moves, &contents)
+                    .with_context(|| format!("Can't undo operation {}", entry.id))?;
+            }
+            Action::Undo { .. } => bail!("Operation {} is an undo itself", entry.id),
+        }
+        self.append(Action::Undo {
+            undone: entry.id.clone(),
+        })
+    }
+
+    fn append(&self, action: Action) -> Result<()> {
+        let now = Local::now();
+        let id = format!(
+            "{}-{}",
+            unique_micros(now.timestamp_micros()),
+            std::process::id()
+        );
+        let time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+        let line = serde_json::to_string(&Entry { id, time, action })?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")?;
+        Ok(())
+    }
+}
+
+/// Write operation to the journal. Failure doesn't fail the operation itself but is reported.
+pub fn record(write: impl FnOnce(&Journal) -> Result<()>) {
+    if let Err(err) = Journal::open().and_then(|journal| write(&journal)) {
+        UI::warning(format!(
+            "failed to record operation to the journal: {err:#}"
+        ));
+    }
+}
+
+/// Timestamp which is greater than the previous one returned in this process, so entries
+/// written in the same microsecond get different ids
+fn unique_micros(micros: i64) -> i64 {
+    static LAST: AtomicI64 = AtomicI64::new(0);
+    let next = |last: i64| micros.max(last + 1);
+    LAST.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+        Some(next(last))
+    })
+    .map_or(micros, next)
+}
+
+fn check_unchanged(id: &str, path: &Path, stamp: &Stamp) -> Result<()> {
+    if !path.exists() {
+        bail!(
+            "Can't undo operation {id}: \"{}\" doesn't exist",
+            path.display()
+        );
+    }
+    if Stamp::new(path)? != *stamp {
+        bail!(
+            "Can't undo operation {id}: \"{}\" was changed since",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tags;
+
+    #[test]
+    fn tags() {
+        let old = "#+title:      A\r\n#+filetags:   :mtg:\r\n\r\nbody";
+        let new = "#+title:      A\r\n#+filetags:   :meeting:\r\n\r\nbody";
+        let tags = Tags::diff(old, new).unwrap();
+        assert_eq!(tags.old, "#+filetags:   :mtg:");
+        assert_eq!(tags.new, "#+filetags:   :meeting:");
+        assert_eq!(tags.restore(new).unwrap(), old);
+        assert!(tags.restore(old).is_none());
+        assert!(Tags::diff(old, old).is_none());
+    }
+}
@@ -0,0 +1,206 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use denote::{FrontMatter, FrontMatterKind, Keywords, Silo};
+use fs_err as fs;
+
+use crate::args::{
+    self,
+    keywords::{KeywordsCommand, SortKeywords},
+};
+use crate::journal::{Tags, record};
+use crate::transaction::{self, Move};
+use crate::ui::{Answer, UI};
+
+/// Change of a single note
+struct Change {
+    path: PathBuf,
+    new_path: PathBuf,
+    content: Option<String>,
+    tags: Option<Tags>,
+}
+
+pub fn keywords(args: &args::Keywords, ui: &mut UI) -> Result<()> {
+    match &args.command {
+        KeywordsCommand::List(args) => list(&args.dir, args.sort, ui),
+        KeywordsCommand::Rename(args) => {
+            replace(&[args.old.as_str()], &args.new, &args.dir, args.accept, ui)
+        }
+        KeywordsCommand::Merge(args) => {
+            let [sources @ .., into, target] = args.keywords.as_slice() else {
+                bail!("Expected keywords to merge, \"into\" and the resulting keyword");
+            };
+            if !into.eq_ignore_ascii_case("into") || sources.is_empty() {
+                bail!("Expected keywords to merge, \"into\" and the resulting keyword");
+            }
+            let sources: Vec<_> = sources.iter().map(String::as_str).collect();
+            replace(&sources, target, &args.dir, args.accept, ui)
+        }
+    }
+}
+
+/// Print keywords with number of files using them
+fn list(dir: &Path, sort: SortKeywords, ui: &UI) -> Result<()> {
+    let scan = Silo::new(dir)
+        .scan()
+        .with_context(|| format!("Failed to scan \"{}\"", dir.display()))?;
+    UI::scan_errors(&scan);
+
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    if let Some(known) = ui.known_keywords() {
+        for keyword in known.iter() {
+            counts.insert(keyword.to_string(), 0);
+        }
+    }
+    for (_, denote) in &scan.notes {
+        for keyword in denote.keywords.iter().flat_map(denote::Keywords::iter) {
+            *counts.entry(keyword.to_string()).or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    if matches!(sort, SortKeywords::Count) {
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    }
+    let width = counts
+        .iter()
+        .map(|(_, count)| count.to_string().len())
+        .max()
+        .unwrap_or_default();
+    for (keyword, count) in counts {
+        println!("{count:>width$}  {keyword}");
+    }
+
+    Ok(())
+}
+
+/// Replace `sources` keywords with `target` in all notes of the directory. Only the keywords
+/// component of file names is changed. Files are renamed and their front matter is rewritten
+/// as a whole.
+fn replace(sources: &[&str], target: &str, dir: &Path, accept: bool, ui: &mut UI) -> Result<()> {
+    let options = ui.slug_options().clone();
+    let mut target_keywords = Keywords::default();
+    if !target_keywords.insert_with(target, &options) {
+        bail!("Keyword \"{target}\" is empty after slugifying");
+    }
+    // Source which is the target itself doesn't need changes
+    let sources: Vec<_> = sources
+        .iter()
+        .filter(|source| !target_keywords.contains_with(source, &options))
+        .collect();
+
+    let scan = Silo::new(dir)
+        .scan()
+        .with_context(|| format!("Failed to scan \"{}\"", dir.display()))?;
+    UI::scan_errors(&scan);
+
+    let mut changes = Vec::new();
+    for (path, mut denote) in scan {
+        let Some(keywords) = denote.keywords.as_mut() else {
+            continue;
+        };
+        if !sources
+            .iter()
+            .any(|source| keywords.contains_with(source, &options))
+        {
+            continue;
+        }
+        for source in &sources {
+            keywords.remove_with(source, &options);
+        }
+        keywords.insert_with(target, &options);
+        let Some(new_path) = with_keywords(&path, keywords) else {
+            continue;
+        };
+
+        // Only text notes have front matter
+        let is_text = denote
+            .extension
+            .as_ref()
+            .and_then(FrontMatterKind::from_extension)
+            .is_some();
+        let (content, tags) = if is_text {
+            let old = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                    UI::warning(format!("skipping \"{}\": {err}", path.display()));
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let content = FrontMatter::replace_keywords(&old, denote.keywords.as_ref());
+            // Tags line is kept in the journal to restore it on undo
+            let tags = content.as_ref().and_then(|new| Tags::diff(&old, new));
+            (content.filter(|_| tags.is_some()), tags)
+        } else {
+            (None, None)
+        };
+        changes.push(Change {
+            path,
+            new_path,
+            content,
+            tags,
+        });
+    }
+
+    if changes.is_empty() {
+        UI::no_action_needed();
+        return Ok(());
+    }
+
+    for change in &changes {
+        let front_matter = if change.content.is_some() {
+            " (front matter)"
+        } else {
+            ""
+        };
+        println!(
+            "{} -> {}{front_matter}",
+            change.path.display(),
+            change.new_path.display()
+        );
+    }
+
+    let question = format!("Apply changes to {} files?", changes.len());
+    if !accept && !ui.confirm(question, Answer::Yes)?.as_bool() {
+        UI::no_action_needed();
+        return Ok(());
+    }
+
+    let moves: Vec<_> = changes
+        .iter()
+        .map(|change| Move {
+            from: change.path.clone(),
+            to: change.new_path.clone(),
+            overwrite: false,
+        })
+        .collect();
+    let tags: Vec<_> = changes.iter().map(|change| change.tags.clone()).collect();
+    let contents: Vec<_> = changes
+        .into_iter()
+        .filter_map(|change| Some((change.new_path, change.content?)))
+        .collect();
+    transaction::apply_with_contents(&moves, &contents)?;
+    record(|journal| journal.record_edits(&moves, &tags));
+
+    Ok(())
+}
+
+/// Path with the keywords component of the file name replaced and other components kept as is
+fn with_keywords(path: &Path, keywords: &Keywords) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let start = name.find("__")?;
+    // Keywords can't contain characters of other prefixes and extension delimiter
+    let end = name[start + 2..]
+        .find(['-', '=', '@', '.'])
+        .map_or(name.len(), |i| start + 2 + i);
+    Some(path.with_file_name(format!("{}{keywords}{}", &name[..start], &name[end..])))
+}
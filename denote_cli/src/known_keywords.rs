@@ -0,0 +1,45 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Registry of known keywords like `denote-known-keywords` in Emacs Denote. It's a file with one
+//! keyword per line, lines starting with "#" are comments.
+
+use std::io;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use denote::Keywords;
+
+use crate::config::Config;
+
+/// Location of the registry: `~/.config/denote/keywords` on Linux
+pub fn path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("denote").join("keywords"))
+}
+
+/// Read the registry. Keywords set in the config replace the registry. Returns `None` if it
+/// doesn't exist.
+pub fn load(config: &Config) -> Result<Option<Keywords>> {
+    if let Some(keywords) = &config.known_keywords {
+        return Ok(Keywords::parse_user_input(keywords.join(",")));
+    }
+    let Some(path) = path() else {
+        return Ok(None);
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read \"{}\"", path.display()));
+        }
+    };
+    let keywords: Vec<_> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    Ok(Keywords::parse_user_input(keywords.join(",")))
+}
@@ -0,0 +1,73 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use denote::{Denote, LinkStyle, Scan, Silo, links};
+use fs_err as fs;
+
+use crate::{args, ui::UI};
+
+pub fn link(args: &args::Link) -> Result<()> {
+    let denote = Denote::try_from_path(&args.file)
+        .with_context(|| format!("Failed to parse \"{}\"", args.file.display()))?;
+    let style = args.style.unwrap_or_else(|| {
+        denote
+            .extension
+            .as_ref()
+            .and_then(LinkStyle::from_extension)
+            .unwrap_or(LinkStyle::Text)
+    });
+    println!("{}", denote.link(style, args.search.as_deref()));
+    Ok(())
+}
+
+pub fn links(args: &args::Links) -> Result<()> {
+    let content = fs::read(&args.file)?;
+    let scan = scan(&args.file, args.dir.as_deref())?;
+
+    for identifier in links::find(String::from_utf8_lossy(&content)) {
+        match links::resolve(&identifier, &scan) {
+            Some(path) => println!("{}", path.display()),
+            None => UI::warning(format!("note {identifier} is not found")),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn backlinks(args: &args::Backlinks) -> Result<()> {
+    let denote = Denote::try_from_path(&args.file)
+        .with_context(|| format!("Failed to parse \"{}\"", args.file.display()))?;
+    let scan = scan(&args.file, args.dir.as_deref())?;
+
+    let backlinks = links::backlinks(&denote.identifier, &scan);
+    for (path, err) in &backlinks.errors {
+        UI::warning(format!("failed to read \"{}\": {err}", path.display()));
+    }
+    for path in backlinks.notes {
+        println!("{}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Scan the given directory or directory of the note
+fn scan(file: &Path, dir: Option<&Path>) -> Result<Scan> {
+    let dir = dir.map_or_else(
+        || match file.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        },
+        Path::to_path_buf,
+    );
+    let scan = Silo::new(&dir)
+        .scan()
+        .with_context(|| format!("Failed to scan \"{}\"", dir.display()))?;
+    UI::scan_errors(&scan);
+    Ok(scan)
+}
@@ -0,0 +1,172 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use denote::{Denote, Silo};
+use serde::Serialize;
+
+use crate::args::{self, list::Format, list::Sort};
+use crate::ui::UI;
+
+const HEADER: [&str; 7] = [
+    "identifier",
+    "date",
+    "signature",
+    "title",
+    "keywords",
+    "extension",
+    "path",
+];
+
+/// Row of the listing
+#[derive(Serialize)]
+struct Note {
+    identifier: String,
+    date: String,
+    signature: Option<String>,
+    title: Option<String>,
+    keywords: Vec<String>,
+    extension: Option<String>,
+    path: PathBuf,
+}
+
+impl Note {
+    fn new(path: PathBuf, denote: &Denote) -> Self {
+        Self {
+            identifier: denote.identifier.to_string(),
+            date: denote
+                .identifier
+                .to_datetime()
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            signature: denote.signature.as_ref().map(|s| s.as_str().to_string()),
+            title: denote.title.as_ref().map(denote::Title::desluggify),
+            keywords: denote
+                .keywords
+                .iter()
+                .flat_map(denote::Keywords::iter)
+                .map(ToString::to_string)
+                .collect(),
+            extension: denote.extension.as_ref().map(|e| e.as_str().to_string()),
+            path,
+        }
+    }
+
+    fn fields(&self) -> [String; 7] {
+        [
+            self.identifier.clone(),
+            self.date.clone(),
+            self.signature.clone().unwrap_or_default(),
+            self.title.clone().unwrap_or_default(),
+            self.keywords.join(","),
+            self.extension.clone().unwrap_or_default(),
+            self.path.display().to_string(),
+        ]
+    }
+}
+
+pub fn list(args: &args::List) -> Result<()> {
+    let scan = Silo::new(&args.dir)
+        .hidden(args.hidden)
+        .dirs(args.dirs)
+        .scan()
+        .with_context(|| format!("Failed to scan \"{}\"", args.dir.display()))?;
+    UI::scan_errors(&scan);
+
+    for (identifier, paths) in scan.duplicates() {
+        let paths: Vec<_> = paths.iter().map(|p| p.display().to_string()).collect();
+        UI::warning(format!(
+            "identifier {identifier} is used by {}",
+            paths.join(", ")
+        ));
+    }
+
+    let mut notes: Vec<_> = scan
+        .into_iter()
+        .filter(|(_, denote)| args.query.as_ref().is_none_or(|q| q.matches(denote)))
+        .collect();
+
+    notes.sort_by(|(_, a), (_, b)| match args.sort {
+        Sort::Id => a.identifier.cmp(&b.identifier),
+        Sort::Title => a
+            .title
+            .as_ref()
+            .map(denote::Title::as_str)
+            .cmp(&b.title.as_ref().map(denote::Title::as_str))
+            .then_with(|| a.identifier.cmp(&b.identifier)),
+        Sort::Signature => a
+            .signature
+            .as_ref()
+            .map(denote::Signature::as_str)
+            .cmp(&b.signature.as_ref().map(denote::Signature::as_str))
+            .then_with(|| a.identifier.cmp(&b.identifier)),
+    });
+    if args.reverse {
+        notes.reverse();
+    }
+
+    let notes: Vec<_> = notes
+        .into_iter()
+        .map(|(path, denote)| Note::new(path, &denote))
+        .collect();
+
+    match args.format {
+        Format::Table => print_table(&notes),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&notes)?),
+        Format::Csv => print_separated(&notes, ',', csv_field),
+        Format::Tsv => print_separated(&notes, '\t', |field| field.replace(['\t', '\n'], " ")),
+    }
+
+    Ok(())
+}
+
+/// Print aligned columns without path
+fn print_table(notes: &[Note]) {
+    let rows: Vec<_> = notes.iter().map(Note::fields).collect();
+    let columns = HEADER.len() - 1;
+    let widths: Vec<_> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([HEADER[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let print_row = |row: &[String]| {
+        let line: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(field, width)| format!("{field:width$}"))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    };
+
+    let header: Vec<_> = HEADER[..columns].iter().map(|h| h.to_uppercase()).collect();
+    print_row(&header);
+    for row in &rows {
+        print_row(&row[..columns]);
+    }
+}
+
+fn print_separated(notes: &[Note], separator: char, escape: impl Fn(&str) -> String) {
+    println!("{}", HEADER.join(&separator.to_string()));
+    for note in notes {
+        let row: Vec<_> = note.fields().iter().map(|f| escape(f)).collect();
+        println!("{}", row.join(&separator.to_string()));
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
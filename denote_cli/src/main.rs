@@ -5,25 +5,59 @@
  */
 
 mod args;
+mod collision;
+mod config;
+mod history;
+mod journal;
+mod keywords;
+mod known_keywords;
+mod links;
+mod list;
+mod plan;
 mod rename;
 mod touch;
+mod transaction;
 mod ui;
 
 use anyhow::Result;
 use clap::Parser;
 
-use args::Args;
+use args::{Args, Command};
+use config::Config;
+use history::{history, undo};
+use keywords::keywords;
+use links::{backlinks, link, links};
+use list::list;
 use rename::rename;
 use touch::touch;
 use ui::UI;
 
 fn main() -> Result<()> {
     let cli = Args::parse();
-    let mut ui = UI::new();
+    let config = Config::load(cli.config.as_deref())?;
+    // Registry is optional, so commands which don't need it still work
+    let known_keywords = known_keywords::load(&config).unwrap_or_else(|err| {
+        UI::warning(format!("failed to load known keywords: {err:#}"));
+        None
+    });
+    let mut ui = UI::new(known_keywords, config.slug_options());
 
-    match cli {
-        Args::Rename(args) => rename(&args, &mut ui)?,
-        Args::Touch(args) => touch(args, &mut ui)?,
+    match cli.command {
+        Command::Backlinks(args) => backlinks(&args)?,
+        Command::History(args) => history(&args)?,
+        Command::Keywords(args) => keywords(&args, &mut ui)?,
+        Command::Link(args) => link(&args)?,
+        Command::Links(args) => links(&args)?,
+        Command::List(args) => list(&args)?,
+        Command::Rename(mut args) => {
+            config.merge_rename(&mut args);
+            rename(&args, &mut ui)?;
+        }
+        Command::Touch(mut args) => {
+            config.merge_touch(&mut args);
+            touch(args, &mut ui)?;
+        }
+        Command::Undo(args) => undo(&args)?,
     }
 
     Ok(())
@@ -0,0 +1,121 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::collision;
+
+/// Renames which would be done by a command
+#[derive(Serialize)]
+#[serde(transparent)]
+pub struct Plan {
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Serialize)]
+pub struct Entry {
+    pub old: PathBuf,
+    /// Missing if new name can't be built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<PathBuf>,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    /// File will be renamed
+    Rename,
+    /// New name is the same as the old one
+    NoOp,
+    /// New name is taken by existing file or by another file of the plan
+    Collision,
+    /// New name can't be built, e.g. file doesn't exist
+    Error,
+}
+
+impl Plan {
+    /// Plan from old paths and new paths or errors explaining why file can't be renamed
+    pub fn new(renames: Vec<(PathBuf, Result<PathBuf, String>)>) -> Self {
+        let targets: Vec<_> = renames
+            .iter()
+            .map(|(old, new)| (old, new.as_ref().ok()))
+            .collect();
+        let statuses: Vec<_> = targets
+            .iter()
+            .enumerate()
+            .map(|(i, &(old, new))| {
+                let Some(new) = new else {
+                    return Status::Error;
+                };
+                let taken = targets
+                    .iter()
+                    .enumerate()
+                    .any(|(j, &(_, other))| i != j && other == Some(new));
+                // Target is another file of the plan which is renamed too
+                let moved_away = targets
+                    .iter()
+                    .any(|&(from, to)| from == new && to.is_some_and(|to| from != to));
+                if old == new {
+                    Status::NoOp
+                } else if taken || (!moved_away && collision::is_taken(Some(old), new)) {
+                    Status::Collision
+                } else {
+                    Status::Rename
+                }
+            })
+            .collect();
+        let entries = renames
+            .into_iter()
+            .zip(statuses)
+            .map(|((old, new), status)| {
+                let (new, error) = match new {
+                    Ok(new) => (Some(new), None),
+                    Err(error) => (None, Some(error)),
+                };
+                Entry {
+                    old,
+                    new,
+                    status,
+                    error,
+                }
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Number of files which can't be renamed because of errors
+    pub fn errors(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == Status::Error)
+            .count()
+    }
+
+    pub fn print(&self) {
+        for entry in &self.entries {
+            let old = entry.old.display();
+            let new = entry
+                .new
+                .as_deref()
+                .unwrap_or_else(|| Path::new(""))
+                .display();
+            match entry.status {
+                Status::Rename => println!("{old} -> {new}"),
+                Status::NoOp => println!("{old} (no changes)"),
+                Status::Collision => println!("{old} -> {new} (collision)"),
+                Status::Error => println!(
+                    "{old} (error: {})",
+                    entry.error.as_deref().unwrap_or_default()
+                ),
+            }
+        }
+    }
+}
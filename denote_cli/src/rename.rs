@@ -4,15 +4,26 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::path::{Path, PathBuf};
+
 use anyhow::{Context, bail};
-use denote::{Denote, Extension, Identifier, Keywords, Signature, Title};
-use fs_err as fs;
+use denote::{Denote, Extension, Identifier, Keywords, Signature, Silo, SlugOptions, Title};
 
-use crate::args;
-use crate::ui::{Answer, UI};
+use crate::args::{self, rename::PlanFormat};
+use crate::collision::{self, OnCollision, Target};
+use crate::journal::record;
+use crate::plan::Plan;
+use crate::transaction::{self, Move};
+use crate::ui::{Answer, RenameAnswer, UI};
 
 pub fn rename(args: &args::Rename, ui: &mut UI) -> anyhow::Result<()> {
-    if args.paths.len() > 1 && unic_id(args.date.as_deref()) {
+    let paths = paths(args, ui.slug_options())?;
+    if paths.is_empty() {
+        UI::no_action_needed();
+        return Ok(());
+    }
+
+    if !args.dry_run && paths.len() > 1 && unic_id(args.date.as_deref()) {
         let accept = ui.confirm(
             "It is not recommended to use one unique identifier for several files\nContinue?",
             Answer::No,
@@ -23,93 +34,360 @@ pub fn rename(args: &args::Rename, ui: &mut UI) -> anyhow::Result<()> {
         }
     }
 
-    for path in &args.paths {
-        if !path.exists() {
-            bail!("File doesn't exists");
+    if args.dry_run {
+        return dry_run(&paths, args, ui);
+    }
+
+    let interactive = !args.non_interactive;
+    let policy = args.on_collision.unwrap_or(if interactive {
+        OnCollision::Ask
+    } else {
+        OnCollision::Refuse
+    });
+
+    let mut accept = args.accept;
+    let mut summary = Summary::default();
+    let mut batch = Batch {
+        sources: paths
+            .iter()
+            .filter_map(|path| path.canonicalize().ok())
+            .collect(),
+        ..Batch::default()
+    };
+
+    for (i, path) in paths.iter().enumerate() {
+        match rename_file(path, args, policy, &mut accept, &batch, ui) {
+            Ok(Outcome::Planned(planned)) => batch.moves.push(planned),
+            Ok(Outcome::Unchanged(kept)) => {
+                summary.unchanged += 1;
+                batch.kept.push(kept);
+            }
+            Ok(Outcome::Skipped(kept)) => {
+                summary.skipped += 1;
+                batch.kept.push(kept);
+            }
+            Ok(Outcome::Quit) => {
+                summary.skipped += paths.len() - i;
+                break;
+            }
+            Err(err) => {
+                UI::error(format!("{}: {err:#}", path.display()));
+                summary.failed += 1;
+                batch.kept.extend(path.canonicalize().ok());
+            }
         }
-        if path.is_dir() {
-            bail!("Renaming directories is not supported");
+    }
+
+    if summary.failed > 0 && !args.partial && !batch.moves.is_empty() {
+        UI::error("nothing is renamed because some files failed, use --partial to rename the rest");
+        summary.skipped += batch.moves.len();
+    } else {
+        match transaction::apply(&batch.moves) {
+            Ok(()) => {
+                summary.renamed = batch.moves.len();
+                if !batch.moves.is_empty() {
+                    record(|journal| journal.record_renames(&batch.moves));
+                }
+            }
+            Err(err) => {
+                UI::error(format!("{err:#}"));
+                summary.failed += batch.moves.len();
+            }
         }
+    }
 
-        let current_name_scheme = Denote::from_path(path);
+    if paths.len() > 1 {
+        println!(
+            "Renamed: {}, unchanged: {}, skipped: {}, failed: {}",
+            summary.renamed, summary.unchanged, summary.skipped, summary.failed
+        );
+    }
+    if summary.failed > 0 {
+        bail!(
+            "Failed to rename {} of {} files",
+            summary.failed,
+            paths.len()
+        );
+    }
 
-        let identifier = if args.date_from_metadata {
-            Identifier::from_file_metadata(path)?
-        } else if let Some(date) = &args.date {
-            Identifier::parse(date).unwrap_or_default()
-        } else {
-            current_name_scheme
-                .as_ref()
-                .map(|cns| cns.identifier.clone())
-                .unwrap_or_default()
-        };
+    Ok(())
+}
 
-        let interactive = !args.non_interactive;
+/// Result of planning rename of a single file
+enum Outcome {
+    Planned(Move),
+    /// New name is the same, contains canonical path of the file
+    Unchanged(PathBuf),
+    /// Renaming is declined, contains canonical path of the file
+    Skipped(PathBuf),
+    /// User asked to stop, the file is not renamed
+    Quit,
+}
 
-        let mut name_scheme = Denote::new(identifier);
+#[derive(Default)]
+struct Summary {
+    renamed: usize,
+    unchanged: usize,
+    skipped: usize,
+    failed: usize,
+}
 
-        if let Some(signature) = &args.signature {
-            name_scheme.signature = Signature::parse(signature);
-        } else if !interactive && let Some(cns) = &current_name_scheme {
-            name_scheme.signature.clone_from(&cns.signature);
-        }
+/// Renames planned so far. Paths are canonical.
+#[derive(Default)]
+struct Batch {
+    /// All files passed to the command
+    sources: Vec<PathBuf>,
+    /// Files which stay in place
+    kept: Vec<PathBuf>,
+    moves: Vec<Move>,
+}
 
-        if let Some(title) = &args.title {
-            name_scheme.title = Title::parse(title);
-        } else if interactive {
-            let file_title = path
-                .file_stem()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
-            let old_title = current_name_scheme
-                .as_ref()
-                .and_then(|ns| ns.title.clone())
-                .map_or(file_title, |title| title.desluggify());
-            name_scheme.title = ui.title_with_old_title(&old_title)?;
-        } else if let Some(cns) = &current_name_scheme {
-            name_scheme.title.clone_from(&cns.title);
+impl Batch {
+    /// Path is taken by a file which stays in place or by a target of another move
+    fn is_taken(&self, source: &Path, path: &Path) -> bool {
+        if self.moves.iter().any(|planned| planned.to == path) {
+            return true;
         }
+        let moved_away =
+            self.sources.iter().any(|s| s == path) && !self.kept.iter().any(|k| k == path);
+        !moved_away && collision::is_taken(Some(source), path)
+    }
+}
+
+/// Plan rename of a single file. `accept` is set when user answers "yes to all".
+fn rename_file(
+    path: &Path,
+    args: &args::Rename,
+    policy: OnCollision,
+    accept: &mut bool,
+    batch: &Batch,
+    ui: &mut UI,
+) -> anyhow::Result<Outcome> {
+    let file_name = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut name_scheme = new_name_scheme(path, args, !args.non_interactive, ui)?;
+    let source = path.canonicalize()?;
+
+    if file_name == name_scheme.to_string_with_order(&args.order) {
+        UI::no_action_needed();
+        return Ok(Outcome::Unchanged(source));
+    }
+
+    let parent = source
+        .parent()
+        .with_context(|| format!("Failed to find parent of dir '{}'", path.to_string_lossy()))?;
+    let path_of = |denote: &Denote| Ok(parent.join(denote.to_string_with_order(&args.order)));
+    let taken = |target: &Path| batch.is_taken(&source, target);
+    let (new_path, overwrite) =
+        match collision::resolve(&mut name_scheme, taken, path_of, policy, ui)? {
+            Target::Free(new_path) => (new_path, false),
+            Target::Overwrite(new_path) => (new_path, true),
+            Target::Skip => {
+                UI::no_action_needed();
+                return Ok(Outcome::Skipped(source));
+            }
+        };
+    let new_file_name = name_scheme.to_string_with_order(&args.order);
+
+    if ui.slug_options().normalize && !Denote::is_normalized_name(path) {
+        UI::not_normalized_name();
+    }
 
-        if let Some(keywords) = &args.keywords {
-            name_scheme.keywords = Keywords::parse_user_input(keywords);
-        } else if interactive {
-            name_scheme.keywords = ui.take_keywords()?;
+    if !*accept {
+        match ui.rename_confirm(&file_name, &new_file_name)? {
+            RenameAnswer::Yes => {}
+            RenameAnswer::All => *accept = true,
+            RenameAnswer::No => {
+                UI::no_action_needed();
+                return Ok(Outcome::Skipped(source));
+            }
+            RenameAnswer::Quit => return Ok(Outcome::Quit),
         }
+    }
+
+    Ok(Outcome::Planned(Move {
+        from: source,
+        to: new_path,
+        overwrite,
+    }))
+}
 
-        if let Some(extension) = &args.extension {
-            name_scheme.extension = Extension::new(extension);
-        } else if let Some(cns) = &current_name_scheme {
-            name_scheme.extension.clone_from(&cns.extension);
-        } else if let Some(ext) = Extension::from_path(path) {
-            name_scheme.extension(ext);
+/// Files to rename: given files and, with `--recursive`, files in given directories
+fn paths(args: &args::Rename, options: &SlugOptions) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for path in &args.paths {
+        if !(args.recursive && path.is_dir()) {
+            paths.push(path.clone());
+            continue;
         }
+        let scan = Silo::new(path)
+            .include(args.include.clone())
+            .exclude(args.exclude.clone())
+            .scan()
+            .with_context(|| format!("Failed to scan \"{}\"", path.display()))?;
+        UI::scan_errors(&scan);
+        let mut found: Vec<_> = scan
+            .non_conforming
+            .into_iter()
+            .map(|(path, _)| path)
+            .chain(scan.notes.into_iter().map(|(path, _)| path))
+            .collect();
+        found.sort();
+        paths.extend(found);
+    }
+    if args.skip_conforming {
+        paths.retain(|path| parse(path, options).is_err());
+    }
+    Ok(paths)
+}
 
-        let file_name = path
-            .file_name()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_default();
+/// Print what would be renamed without touching the file system
+fn dry_run(paths: &[PathBuf], args: &args::Rename, ui: &mut UI) -> anyhow::Result<()> {
+    let mut renames = Vec::with_capacity(paths.len());
+    for path in paths {
+        // Error of one file doesn't hide the plan for others
+        let new_path = new_name_scheme(path, args, false, ui)
+            .map(|name_scheme| path.with_file_name(name_scheme.to_string_with_order(&args.order)))
+            .map_err(|err| format!("{err:#}"));
+        renames.push((path.clone(), new_path));
+    }
 
-        let new_file_name = name_scheme.to_string();
+    let plan = Plan::new(renames);
+    match args.format {
+        PlanFormat::Text => plan.print(),
+        PlanFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+    }
+    let errors = plan.errors();
+    if errors > 0 {
+        bail!("Failed to plan rename of {errors} of {} files", paths.len());
+    }
+    Ok(())
+}
 
-        if file_name == new_file_name {
-            UI::no_action_needed();
-            return Ok(());
+/// Build new name scheme from arguments, current name and user input
+fn new_name_scheme(
+    path: &Path,
+    args: &args::Rename,
+    interactive: bool,
+    ui: &mut UI,
+) -> anyhow::Result<Denote> {
+    if !path.exists() {
+        bail!("File doesn't exists");
+    }
+    let is_dir = path.is_dir();
+    if is_dir && args.extension.is_some() {
+        bail!("Directories can't have extension");
+    }
+
+    let current_name_scheme = match parse(path, ui.slug_options()) {
+        Ok(name_scheme) => Some(name_scheme),
+        Err(denote::Error::NotDenoteName(_)) => None,
+        Err(err) => {
+            UI::warning(format!(
+                "File name \"{}\" looks like denote name scheme but {err}",
+                path.display()
+            ));
+            None
         }
+    };
 
-        if !args.accept && !ui.rename_confirm(&file_name, &new_file_name)?.as_bool() {
-            UI::no_action_needed();
-            return Ok(());
+    let mut identifier = if args.date_from_metadata {
+        Identifier::from_file_metadata(path)?
+    } else if let Some(date) = &args.date {
+        Identifier::try_parse(date).context("Failed to parse identifier")?
+    } else {
+        current_name_scheme
+            .as_ref()
+            .map(|cns| cns.identifier.clone())
+            .unwrap_or_default()
+    };
+    if let Some(precision) = args.precision {
+        identifier = identifier.with_precision(precision);
+    }
+
+    let mut name_scheme = Denote::new(identifier);
+
+    if let Some(signature) = &args.signature {
+        name_scheme.signature = Signature::parse_with(signature, ui.slug_options());
+    } else if !interactive && let Some(cns) = &current_name_scheme {
+        name_scheme.signature.clone_from(&cns.signature);
+    }
+
+    if let Some(title) = &args.title {
+        name_scheme.title = Title::parse_with(title, ui.slug_options());
+    } else if interactive {
+        let file_title = if is_dir {
+            path.file_name()
+        } else {
+            path.file_stem()
         }
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+        let old_title = current_name_scheme
+            .as_ref()
+            .and_then(|ns| ns.title.clone())
+            .map_or(file_title, |title| title.desluggify());
+        name_scheme.title = ui.title_with_old_title(&old_title)?;
+    } else if let Some(cns) = &current_name_scheme {
+        name_scheme.title.clone_from(&cns.title);
+    }
 
-        let parent = path.parent().with_context(|| {
-            format!("Failed to find parent of dir '{}'", path.to_string_lossy())
-        })?;
-        let new_path = parent.join(&new_file_name);
+    name_scheme.keywords = keywords(args, current_name_scheme.as_ref(), interactive, ui)?;
 
-        fs::rename(path, new_path)?;
+    if is_dir {
+        // Dot in directory name is not an extension
+    } else if let Some(extension) = &args.extension {
+        name_scheme.extension = Extension::new(extension);
+    } else if let Some(cns) = &current_name_scheme {
+        name_scheme.extension.clone_from(&cns.extension);
+    } else if let Some(ext) = Extension::from_path(path) {
+        name_scheme.extension(ext);
     }
 
-    Ok(())
+    Ok(name_scheme)
+}
+
+/// Keywords from arguments, user input or current name with added and removed keywords
+fn keywords(
+    args: &args::Rename,
+    current_name_scheme: Option<&Denote>,
+    interactive: bool,
+    ui: &mut UI,
+) -> anyhow::Result<Option<Keywords>> {
+    let edit_keywords = !args.add_keyword.is_empty() || !args.remove_keyword.is_empty();
+    let keywords = if let Some(keywords) = &args.keywords {
+        Keywords::parse_user_input_with(keywords, ui.slug_options())
+    } else if args.clear_keywords {
+        None
+    } else if interactive && !edit_keywords {
+        ui.take_keywords()?
+    } else {
+        current_name_scheme.and_then(|cns| cns.keywords.clone())
+    };
+    if !edit_keywords {
+        return Ok(keywords);
+    }
+
+    let mut keywords = keywords.unwrap_or_default();
+    for keyword in &args.add_keyword {
+        keywords.insert_with(keyword, ui.slug_options());
+    }
+    for keyword in &args.remove_keyword {
+        keywords.remove_with(keyword, ui.slug_options());
+    }
+    Ok(Some(keywords).filter(|k| !k.is_empty()))
+}
+
+/// Parse name of file or directory
+fn parse(path: &Path, options: &SlugOptions) -> Result<Denote, denote::Error> {
+    if path.is_dir() {
+        Denote::try_from_dir_path_with(path, options)
+    } else {
+        Denote::try_from_path_with(path, options)
+    }
 }
 
 fn unic_id(date: Option<&str>) -> bool {
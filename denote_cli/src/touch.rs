@@ -4,34 +4,42 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::path::Path;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
 use std::process::Stdio;
 
-use anyhow::{Context, Result};
-use denote::{Denote, Extension, Identifier, Keywords, Signature, Title};
+use anyhow::{Context, Result, bail};
+use denote::{
+    Denote, Extension, FrontMatter, FrontMatterKind, Identifier, Keywords, Signature, Title,
+};
 use fs_err as fs;
 
-use crate::{args, ui::UI};
+use crate::collision::{self, OnCollision, Target};
+use crate::{args, journal::record, ui::UI};
 
 pub fn touch(args: args::Touch, ui: &mut UI) -> anyhow::Result<()> {
-    let identifier = Identifier::parse(&args.date).context("Failed to parse identifier")?;
+    let mut identifier = Identifier::try_parse(&args.date).context("Failed to parse identifier")?;
+    if let Some(precision) = args.precision {
+        identifier = identifier.with_precision(precision);
+    }
 
     let interactive = !args.non_interactive;
 
     let mut name_scheme = Denote::new(identifier);
 
     if let Some(signature) = args.signature {
-        name_scheme.signature = Signature::parse(&signature);
+        name_scheme.signature = Signature::parse_with(&signature, ui.slug_options());
     }
 
     if let Some(title) = args.title {
-        name_scheme.title = Title::parse(&title);
+        name_scheme.title = Title::parse_with(&title, ui.slug_options());
     } else if interactive {
         name_scheme.title = ui.take_title()?;
     }
 
     if let Some(keywords) = args.keywords {
-        name_scheme.keywords = Keywords::parse_user_input(&keywords);
+        name_scheme.keywords = Keywords::parse_user_input_with(&keywords, ui.slug_options());
     } else if interactive {
         name_scheme.keywords = ui.take_keywords()?;
     }
@@ -42,14 +50,66 @@ pub fn touch(args: args::Touch, ui: &mut UI) -> anyhow::Result<()> {
         name_scheme.extension = ui.take_extension()?;
     }
 
-    let file_name = name_scheme.to_string();
-
-    if !args.accept && !ui.create_file_confirm(&file_name)?.as_bool() {
+    let policy = args.on_collision.unwrap_or(if interactive {
+        OnCollision::Ask
+    } else {
+        OnCollision::Refuse
+    });
+    let dir = args.dir.clone().unwrap_or_default();
+    let path_of = |denote: &Denote| {
+        let subdir = match &args.subdir {
+            Some(pattern) => subdir(&denote.identifier, pattern)?,
+            None => PathBuf::new(),
+        };
+        Ok(dir
+            .join(subdir)
+            .join(denote.to_string_with_order(&args.order)))
+    };
+    let (file_name, overwrite) = match collision::resolve(
+        &mut name_scheme,
+        |path| collision::is_taken(None, path),
+        path_of,
+        policy,
+        ui,
+    )? {
+        Target::Free(path) => (path, false),
+        Target::Overwrite(path) => (path, true),
+        Target::Skip => {
+            UI::no_action_needed();
+            return Ok(());
+        }
+    };
+
+    if !args.accept && !ui.create_file_confirm(file_name.display())?.as_bool() {
         UI::no_action_needed();
         return Ok(());
     }
 
-    fs::File::create(&file_name)?;
+    let front_matter_kind = args.front_matter.or_else(|| {
+        name_scheme
+            .extension
+            .as_ref()
+            .and_then(FrontMatterKind::from_extension)
+    });
+    let content = match front_matter_kind {
+        Some(kind) if !args.no_front_matter => FrontMatter::from(&name_scheme).render(kind),
+        _ => String::new(),
+    };
+
+    if let Some(parent) = file_name.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if overwrite {
+        fs::write(&file_name, content)?;
+    } else {
+        // Never truncate a file created after the collision check
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&file_name)?;
+        file.write_all(content.as_bytes())?;
+    }
+    record(|journal| journal.record_create(&file_name));
 
     if args.open {
         open_file(&file_name)?;
@@ -58,6 +118,26 @@ pub fn touch(args: args::Touch, ui: &mut UI) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Subdirectory built from identifier date and strftime-like pattern. It must be relative and
+/// stay inside the notes directory.
+fn subdir(identifier: &Identifier, pattern: &str) -> Result<PathBuf> {
+    let mut subdir = String::new();
+    if write!(subdir, "{}", identifier.to_datetime().format(pattern)).is_err() {
+        bail!("Invalid subdirectory pattern \"{pattern}\"");
+    }
+    let subdir = PathBuf::from(subdir);
+    if !subdir
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        bail!(
+            "Subdirectory \"{}\" from pattern \"{pattern}\" is outside of the notes directory",
+            subdir.display()
+        );
+    }
+    Ok(subdir)
+}
+
 pub fn open_file(file_name: impl AsRef<Path>) -> Result<()> {
     let editor = std::env::var("EDITOR").context("EDITOR environment variable don't set")?;
     let mut cmd = std::process::Command::new(editor);
@@ -0,0 +1,335 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Apply several renames as a whole: either all of them succeed or the file system is restored.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use crate::collision::{self, rename_no_replace};
+use crate::ui::UI;
+
+/// Planned rename
+pub struct Move {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// Replace existing file at `to` which is not moved by the transaction
+    pub overwrite: bool,
+}
+
+/// Rename performed during the transaction
+struct Step {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+#[derive(Default)]
+struct Transaction {
+    steps: Vec<Step>,
+    /// Replaced files moved aside, removed on commit
+    backups: Vec<PathBuf>,
+}
+
+/// Apply all moves. Chains like A->B, B->C are ordered, cycles like A->B, B->A go through
+/// temporary names. If any rename fails, already applied renames are reverted.
+pub fn apply(moves: &[Move]) -> Result<()> {
+    check(moves)?;
+
+    let mut transaction = Transaction::default();
+    if let Err(err) = transaction.run(moves) {
+        if let Err(rollback_err) = transaction.rollback() {
+            UI::error(format!("failed to roll back renames: {rollback_err:#}"));
+        }
+        return Err(err.context("All renames are reverted"));
+    }
+    transaction.commit();
+    Ok(())
+}
+
+/// Apply moves and replace contents of files at their new paths. Contents are written to
+/// temporary files before any rename, so either everything is applied or nothing is changed.
+pub fn apply_with_contents(moves: &[Move], contents: &[(PathBuf, String)]) -> Result<()> {
+    let mut rewrites = Vec::with_capacity(contents.len());
+    let result = write_temporary(contents, &mut rewrites).and_then(|()| {
+        apply(moves)?;
+        if let Err(err) = apply(&rewrites) {
+            let reverse: Vec<_> = moves
+                .iter()
+                .map(|m| Move {
+                    from: m.to.clone(),
+                    to: m.from.clone(),
+                    overwrite: false,
+                })
+                .collect();
+            if let Err(rollback_err) = apply(&reverse) {
+                UI::error(format!("failed to roll back renames: {rollback_err:#}"));
+            }
+            return Err(err);
+        }
+        Ok(())
+    });
+    if result.is_err() {
+        for rewrite in &rewrites {
+            // Temporary files are left only if the contents were not applied
+            let _ = std::fs::remove_file(&rewrite.from);
+        }
+    }
+    result
+}
+
+/// Write contents to temporary files next to their targets
+fn write_temporary(contents: &[(PathBuf, String)], rewrites: &mut Vec<Move>) -> Result<()> {
+    for (path, content) in contents {
+        let temporary = temporary_path(path)?;
+        rewrites.push(Move {
+            from: temporary.clone(),
+            to: path.clone(),
+            overwrite: true,
+        });
+        std::fs::write(&temporary, content)
+            .with_context(|| format!("Failed to write \"{}\"", temporary.display()))?;
+    }
+    Ok(())
+}
+
+/// Check that targets are unique and free or freed by the transaction itself
+fn check(moves: &[Move]) -> Result<()> {
+    for (i, m) in moves.iter().enumerate() {
+        if moves[..i].iter().any(|other| other.to == m.to) {
+            bail!("Several files would be renamed to \"{}\"", m.to.display());
+        }
+        let freed = moves.iter().any(|other| other.from == m.to);
+        if !m.overwrite && !freed && collision::is_taken(Some(&m.from), &m.to) {
+            bail!("File \"{}\" already exists", m.to.display());
+        }
+    }
+    Ok(())
+}
+
+impl Transaction {
+    fn run(&mut self, moves: &[Move]) -> Result<()> {
+        for m in moves.iter().filter(|m| m.overwrite) {
+            let freed = moves.iter().any(|other| other.from == m.to);
+            if !freed && collision::is_taken(Some(&m.from), &m.to) {
+                let backup = temporary_path(&m.to)?;
+                self.rename(&m.to, &backup)?;
+                self.backups.push(backup);
+            }
+        }
+
+        let mut pending: Vec<(PathBuf, &Path)> = moves
+            .iter()
+            .filter(|m| m.from != m.to)
+            .map(|m| (m.from.clone(), m.to.as_path()))
+            .collect();
+
+        while !pending.is_empty() {
+            // Move which target is not a source of another pending move
+            let ready = pending
+                .iter()
+                .position(|(_, to)| !pending.iter().any(|(from, _)| from == to));
+            if let Some(i) = ready {
+                let (from, to) = pending.remove(i);
+                self.rename(&from, to)?;
+            } else {
+                // Only cycles are left. Move one file aside to break the cycle.
+                let temporary = temporary_path(&pending[0].0)?;
+                let from = pending[0].0.clone();
+                self.rename(&from, &temporary)?;
+                pending[0].0 = temporary;
+            }
+        }
+        Ok(())
+    }
+
+    fn rename(&mut self, from: &Path, to: &Path) -> Result<()> {
+        rename_no_replace(from, to).with_context(|| {
+            format!(
+                "Failed to rename \"{}\" to \"{}\"",
+                from.display(),
+                to.display()
+            )
+        })?;
+        self.steps.push(Step {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<()> {
+        while let Some(step) = self.steps.pop() {
+            std::fs::rename(&step.to, &step.from).with_context(|| {
+                format!(
+                    "Failed to rename \"{}\" back to \"{}\"",
+                    step.to.display(),
+                    step.from.display()
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn commit(self) {
+        for backup in self.backups {
+            if let Err(err) = std::fs::remove_file(&backup) {
+                UI::warning(format!(
+                    "failed to remove replaced file \"{}\": {err}",
+                    backup.display()
+                ));
+            }
+        }
+    }
+}
+
+/// Free hidden name in the same directory
+fn temporary_path(path: &Path) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .context("Path doesn't have file name")?
+        .to_string_lossy();
+    let id = std::process::id();
+    (0..u32::MAX)
+        .map(|i| path.with_file_name(format!(".{name}.denote-{id}-{i}.tmp")))
+        .find(|temporary| temporary.symlink_metadata().is_err())
+        .context("Failed to find temporary name")
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Path;
+
+    use super::{Move, apply, apply_with_contents};
+
+    fn write(dir: &Path, files: &[(&str, &str)]) {
+        for (name, content) in files {
+            fs::write(dir.join(name), content).unwrap();
+        }
+    }
+
+    fn moves(dir: &Path, renames: &[(&str, &str)]) -> Vec<Move> {
+        renames
+            .iter()
+            .map(|(from, to)| Move {
+                from: dir.join(from),
+                to: dir.join(to),
+                overwrite: false,
+            })
+            .collect()
+    }
+
+    /// Names and contents of files in the directory, sorted by name
+    fn files(dir: &Path) -> Vec<(String, String)> {
+        let mut files: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy().to_string();
+                (name, fs::read_to_string(path).unwrap())
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn pairs(files: &[(&str, &str)]) -> Vec<(String, String)> {
+        files
+            .iter()
+            .map(|(name, content)| ((*name).to_string(), (*content).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn swap() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write(dir, &[("a", "1"), ("b", "2")]);
+        apply(&moves(dir, &[("a", "b"), ("b", "a")])).unwrap();
+        assert_eq!(files(dir), pairs(&[("a", "2"), ("b", "1")]));
+    }
+
+    #[test]
+    fn cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write(dir, &[("a", "1"), ("b", "2"), ("c", "3")]);
+        apply(&moves(dir, &[("a", "b"), ("b", "c"), ("c", "a")])).unwrap();
+        assert_eq!(files(dir), pairs(&[("a", "3"), ("b", "1"), ("c", "2")]));
+    }
+
+    #[test]
+    fn chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write(dir, &[("a", "1"), ("b", "2")]);
+        apply(&moves(dir, &[("a", "b"), ("b", "c")])).unwrap();
+        assert_eq!(files(dir), pairs(&[("b", "1"), ("c", "2")]));
+    }
+
+    #[test]
+    fn rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write(dir, &[("a", "1"), ("b", "2"), ("c", "3")]);
+        let mut moves = moves(dir, &[("a", "b"), ("b", "x"), ("c", "y")]);
+        moves[2].from = dir.join("missing");
+        assert!(apply(&moves).is_err());
+        assert_eq!(files(dir), pairs(&[("a", "1"), ("b", "2"), ("c", "3")]));
+    }
+
+    #[test]
+    fn overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write(dir, &[("a", "1"), ("b", "2"), ("c", "3")]);
+        let mut moves = moves(dir, &[("a", "b")]);
+        moves[0].overwrite = true;
+        apply(&moves).unwrap();
+        assert_eq!(files(dir), pairs(&[("b", "1"), ("c", "3")]));
+
+        // Replaced file is restored if the batch fails
+        moves[0] = Move {
+            from: dir.join("b"),
+            to: dir.join("c"),
+            overwrite: true,
+        };
+        moves.push(Move {
+            from: dir.join("missing"),
+            to: dir.join("d"),
+            overwrite: false,
+        });
+        assert!(apply(&moves).is_err());
+        assert_eq!(files(dir), pairs(&[("b", "1"), ("c", "3")]));
+    }
+
+    #[test]
+    fn contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write(dir, &[("a", "1"), ("b", "2")]);
+        let moves = moves(dir, &[("a", "c")]);
+        apply_with_contents(&moves, &[(dir.join("c"), "3".to_string())]).unwrap();
+        assert_eq!(files(dir), pairs(&[("b", "2"), ("c", "3")]));
+
+        // Nothing is renamed if contents can't be written
+        let moves = self::moves(dir, &[("c", "d")]);
+        let contents = [(dir.join("missing").join("d"), "4".to_string())];
+        assert!(apply_with_contents(&moves, &contents).is_err());
+        assert_eq!(files(dir), pairs(&[("b", "2"), ("c", "3")]));
+    }
+
+    #[test]
+    fn taken() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write(dir, &[("a", "1"), ("b", "2")]);
+        assert!(apply(&moves(dir, &[("a", "b")])).is_err());
+        assert!(apply(&moves(dir, &[("a", "c"), ("b", "c")])).is_err());
+        assert_eq!(files(dir), pairs(&[("a", "1"), ("b", "2")]));
+    }
+}
@@ -6,25 +6,42 @@
 
 use std::fmt;
 use std::io::{self, Write};
+use std::path::Path;
 
 use Answer::{No, Yes};
 use anyhow::{Context, Result};
-use denote::{Extension, Keywords, Title};
+use denote::{Extension, Keywords, Scan, SlugOptions, Title};
+
+use crate::collision::OnCollision;
 
 /// User Interface
 pub struct UI {
     stdout: io::Stdout,
     stdin: io::Stdin,
+    known_keywords: Option<Keywords>,
+    slug_options: SlugOptions,
 }
 
 impl UI {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(known_keywords: Option<Keywords>, slug_options: SlugOptions) -> Self {
         Self {
             stdin: io::stdin(),
             stdout: io::stdout(),
+            known_keywords,
+            slug_options,
         }
     }
 
+    /// Keywords from the registry or the config
+    pub(crate) const fn known_keywords(&self) -> Option<&Keywords> {
+        self.known_keywords.as_ref()
+    }
+
+    /// Options to slugify title, signature and keywords given by user
+    pub(crate) const fn slug_options(&self) -> &SlugOptions {
+        &self.slug_options
+    }
+
     pub(crate) fn confirm(
         &mut self,
         question: impl fmt::Display,
@@ -44,22 +61,47 @@ impl UI {
         })
     }
 
+    /// Ask to rename file. Besides yes and no user can accept all remaining files or quit.
     pub fn rename_confirm(
         &mut self,
         old_file_name: impl fmt::Display,
         new_file_name: impl fmt::Display,
-    ) -> Result<Answer> {
+    ) -> Result<RenameAnswer> {
         println!(
             "Old name \"{old_file_name}\"\n\
             New name \"{new_file_name}\""
         );
-        self.confirm("Accept?", Yes)
+        self.print("Accept? [Y]es, [n]o, yes to [a]ll, [q]uit: ")?;
+        let mut response = self.read_line()?;
+        response.make_ascii_lowercase();
+        Ok(match response.trim() {
+            "" | "y" | "yes" => RenameAnswer::Yes,
+            "a" | "all" => RenameAnswer::All,
+            "q" | "quit" => RenameAnswer::Quit,
+            _ => RenameAnswer::No,
+        })
     }
 
     pub fn create_file_confirm(&mut self, file_name: impl fmt::Display) -> Result<Answer> {
         self.confirm(format!("Create file \"{file_name}\"?"), Yes)
     }
 
+    /// Ask what to do with existing file. Skip by default.
+    pub(crate) fn collision(&mut self, path: &Path) -> Result<OnCollision> {
+        self.print(format!(
+            "File \"{}\" already exists. [o]verwrite, [s]kip, [b]ump identifier or [r]efuse? [S] ",
+            path.display()
+        ))?;
+        let mut response = self.read_line()?;
+        response.make_ascii_lowercase();
+        Ok(match response.trim() {
+            "o" | "overwrite" => OnCollision::Overwrite,
+            "b" | "bump" => OnCollision::Bump,
+            "r" | "refuse" => OnCollision::Refuse,
+            _ => OnCollision::Skip,
+        })
+    }
+
     pub(crate) fn title_with_old_title<S>(&mut self, old_title: S) -> Result<Option<Title>>
     where
         S: AsRef<str>,
@@ -72,23 +114,50 @@ impl UI {
         } else {
             &input
         };
-        Ok(Title::parse(title))
+        Ok(Title::parse_with(title, &self.slug_options))
     }
 
     pub(crate) fn no_action_needed() {
         println!("No action needed");
     }
 
+    pub(crate) fn not_normalized_name() {
+        println!("File name is not in Unicode normalization form C and will be normalized");
+    }
+
+    pub(crate) fn warning(message: impl fmt::Display) {
+        eprintln!("Warning: {message}");
+    }
+
+    /// Warn about directories and entries which were skipped while scanning
+    pub(crate) fn scan_errors(scan: &Scan) {
+        for (path, err) in &scan.errors {
+            Self::warning(format!("failed to read \"{}\": {err}", path.display()));
+        }
+    }
+
+    pub(crate) fn error(message: impl fmt::Display) {
+        eprintln!("Error: {message}");
+    }
+
     pub(crate) fn take_title(&mut self) -> Result<Option<Title>> {
         self.print("Title: ")?;
         let input = self.read_line()?;
-        Ok(Title::parse(&input))
+        Ok(Title::parse_with(&input, &self.slug_options))
     }
 
     pub(crate) fn take_keywords(&mut self) -> Result<Option<Keywords>> {
         self.print("Keywords: ")?;
         let input = self.read_line()?;
-        Ok(Keywords::parse_user_input(&input))
+        let keywords = Keywords::parse_user_input_with(&input, &self.slug_options);
+        if let Some(known) = &self.known_keywords {
+            for keyword in keywords.iter().flat_map(Keywords::iter) {
+                if !known.contains(keyword) {
+                    Self::warning(format!("unknown keyword \"{keyword}\""));
+                }
+            }
+        }
+        Ok(keywords)
     }
 
     pub(crate) fn take_extension(&mut self) -> Result<Option<Extension>> {
@@ -112,6 +181,15 @@ impl UI {
     }
 }
 
+pub enum RenameAnswer {
+    Yes,
+    No,
+    /// Yes for this and all following files
+    All,
+    /// No for this and all following files
+    Quit,
+}
+
 pub enum Answer {
     Yes,
     No,